@@ -21,6 +21,7 @@ import { getWrapInstructionAsync } from "../../app/src/generated/instructions/wr
 import { getUnwrapInstructionAsync } from "../../app/src/generated/instructions/unwrap";
 import {
     createConfigureConfidentialInstruction,
+    createVerifyPubkeyValidityInstruction,
     createApplyPendingBalanceInstruction
 } from "../../app/src/confidential";
 
@@ -30,6 +31,10 @@ const rpc = createSolanaRpc(CONNECTION_URL);
 
 // Program ID
 const PROGRAM_ID = address("D3FaNQVD8NZC6CFT1AS8Rq2G26iAGZ19CgLJXNMfGAjY");
+const METADATA_PROGRAM_ID = address("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Wrappers per registry page (RegistryPage::CAPACITY)
+const REGISTRY_PAGE_CAPACITY = 30n;
 
 // Helper to create a Noop Signer
 const createNoopSigner = (addr: Address): TransactionSigner => ({
@@ -49,6 +54,76 @@ const deriveVaultPda = async (originalMint: Address): Promise<Address> => {
     return vaultPda;
 };
 
+// Helper to derive the program config PDA
+const deriveProgramConfigPda = async (): Promise<Address> => {
+    const [programConfigPda] = await getProgramDerivedAddress({
+        programAddress: PROGRAM_ID,
+        seeds: [new TextEncoder().encode("program_config")]
+    });
+    return programConfigPda;
+};
+
+// Helper to derive the registry page the next wrapper is appended to
+const deriveCurrentRegistryPagePda = async (programConfig: Address): Promise<Address> => {
+    const { value } = await rpc.getAccountInfo(programConfig, { encoding: "base64" }).send();
+    if (!value) {
+        throw new Error("Program config is not initialized");
+    }
+    // 8 discriminator + 32 factory_admin, then wrapper_count (u64 LE)
+    const data = Buffer.from(value.data[0], "base64");
+    const page = data.readBigUInt64LE(40) / REGISTRY_PAGE_CAPACITY;
+    const pageBytes = Buffer.alloc(8);
+    pageBytes.writeBigUInt64LE(page);
+    const [registryPagePda] = await getProgramDerivedAddress({
+        programAddress: PROGRAM_ID,
+        seeds: [new TextEncoder().encode("registry"), pageBytes]
+    });
+    return registryPagePda;
+};
+
+// Helper to look up the token program that owns a mint
+const getMintTokenProgram = async (mint: Address): Promise<Address> => {
+    const { value } = await rpc.getAccountInfo(mint, { encoding: "base64" }).send();
+    if (!value) {
+        throw new Error("Mint account not found");
+    }
+    return value.owner;
+};
+
+// Helper to derive the Metaplex metadata PDA, if that account exists
+const findOriginalMetadata = async (originalMint: Address): Promise<Address | undefined> => {
+    const [metadataPda] = await getProgramDerivedAddress({
+        programAddress: METADATA_PROGRAM_ID,
+        seeds: [
+            new TextEncoder().encode("metadata"),
+            new PublicKey(METADATA_PROGRAM_ID).toBytes(),
+            new PublicKey(originalMint).toBytes()
+        ]
+    });
+    const { value } = await rpc.getAccountInfo(metadataPda, { encoding: "base64" }).send();
+    return value ? metadataPda : undefined;
+};
+
+// Helper to derive a referrer's balance PDA for a wrapper
+const deriveReferrerBalancePda = async (originalMint: Address, referrer: Address): Promise<Address> => {
+    const [wrapperConfigPda] = await getProgramDerivedAddress({
+        programAddress: PROGRAM_ID,
+        seeds: [
+            new TextEncoder().encode("config"),
+            new PublicKey(originalMint).toBytes()
+        ]
+    });
+    const [referrerBalancePda] = await getProgramDerivedAddress({
+        programAddress: PROGRAM_ID,
+        seeds: [
+            new TextEncoder().encode("referrer"),
+            new PublicKey(wrapperConfigPda).toBytes(),
+            new PublicKey(referrer).toBytes()
+        ]
+    });
+    return referrerBalancePda;
+};
+
 // Input validation helpers
 const isValidSolanaAddress = (addr: string): boolean => {
     try {
@@ -69,6 +144,21 @@ const isValidAmount = (amount: string): boolean => {
 };
 
 
+// maxFee is optional; without it the transaction accepts any fee
+const NO_FEE_LIMIT = 18446744073709551615n;
+
+const isValidMaxFee = (maxFee: string | undefined): boolean => {
+    if (maxFee === undefined) return true;
+    try {
+        return BigInt(maxFee) >= 0n;
+    } catch {
+        return false;
+    }
+};
+
+const isValidDeadline = (deadline: number | undefined): boolean =>
+    deadline === undefined || Number.isInteger(deadline);
+
 // Helper to convert @solana/kit CompiledTransactionMessage to @solana/web3.js v1 MessageV0
 const toWeb3JsMessageV0 = (compiledMessage: any): MessageV0 => {
     // Audit: Validate input presence
@@ -101,7 +191,16 @@ const toWeb3JsMessageV0 = (compiledMessage: any): MessageV0 => {
 export const initializeController = async (req: Request, res: Response) => {
     console.log("Initialize controller called");
     try {
-        const { payer, originalMint, wrapFeeBps, unwrapFeeBps, auditorElgamalPubkey } = req.body;
+        const {
+            payer,
+            originalMint,
+            wrapFeeBps,
+            unwrapFeeBps,
+            auditorElgamalPubkey,
+            autoApproveNewAccounts,
+            metadataPrefix,
+            timelockDelay,
+        } = req.body;
         console.log("Request body:", req.body);
 
         if (!payer || !originalMint) {
@@ -114,12 +213,22 @@ export const initializeController = async (req: Request, res: Response) => {
         const payerAddress = address(payer);
         const originalMintAddress = address(originalMint);
 
+        const programConfigPda = await deriveProgramConfigPda();
+        const registryPagePda = await deriveCurrentRegistryPagePda(programConfigPda);
+
         const ix = await getInitializeInstructionAsync({
             user: createNoopSigner(payerAddress),
+            programConfig: programConfigPda,
+            registryPage: registryPagePda,
             originalMint: originalMintAddress,
+            originalMetadata: await findOriginalMetadata(originalMintAddress),
+            tokenProgram: await getMintTokenProgram(originalMintAddress),
             wrapFeeBps: wrapFeeBps || 0,
             unwrapFeeBps: unwrapFeeBps || 0,
             auditorElgamalPubkey: auditorElgamalPubkey ? new Uint8Array(Buffer.from(auditorElgamalPubkey, 'base64')) : null,
+            autoApproveNewAccounts: autoApproveNewAccounts ?? true,
+            metadataPrefix: metadataPrefix ?? "c-",
+            timelockDelay: BigInt(timelockDelay ?? 0),
         });
 
         console.log("Getting latest blockhash for initialize...");
@@ -152,7 +261,7 @@ export const initializeController = async (req: Request, res: Response) => {
 
 export const wrapController = async (req: Request, res: Response) => {
     try {
-        const { payer, originalMint, amount, userOriginalAccount } = req.body; // Logs removed for brevity but logic remains same
+        const { payer, originalMint, amount, userOriginalAccount, maxFee, deadline, referrer, feeTreasury } = req.body; // Logs removed for brevity but logic remains same
         console.log("Wrap controller called");
         console.log("Request body:", req.body);
 
@@ -169,10 +278,15 @@ export const wrapController = async (req: Request, res: Response) => {
         if (!isValidSolanaAddress(originalMint)) return res.status(400).json({ error: "Invalid originalMint address format" });
         if (userOriginalAccount && !isValidSolanaAddress(userOriginalAccount)) return res.status(400).json({ error: "Invalid userOriginalAccount address format" });
         if (!isValidAmount(amount)) return res.status(400).json({ error: "Invalid amount: must be a positive integer" });
+        if (!isValidMaxFee(maxFee)) return res.status(400).json({ error: "Invalid maxFee: must be a non-negative integer" });
+        if (!isValidDeadline(deadline)) return res.status(400).json({ error: "Invalid deadline: must be a unix timestamp in seconds" });
+        if (referrer && !isValidSolanaAddress(referrer)) return res.status(400).json({ error: "Invalid referrer address format" });
+        if (feeTreasury && !isValidSolanaAddress(feeTreasury)) return res.status(400).json({ error: "Invalid feeTreasury address format" });
 
         const payerAddress = address(payer);
         const originalMintAddress = address(originalMint);
         const amountBigInt = BigInt(amount);
+        const tokenProgram = await getMintTokenProgram(originalMintAddress);
 
         // Derive userOriginalAccount if not provided
         let userOriginalAccountAddress: Address;
@@ -181,7 +295,9 @@ export const wrapController = async (req: Request, res: Response) => {
         } else {
             const ata = getAssociatedTokenAddressSync(
                 new PublicKey(originalMintAddress),
-                new PublicKey(payerAddress)
+                new PublicKey(payerAddress),
+                false,
+                new PublicKey(tokenProgram)
             );
             userOriginalAccountAddress = address(ata.toBase58());
         }
@@ -195,6 +311,11 @@ export const wrapController = async (req: Request, res: Response) => {
             user: createNoopSigner(payerAddress),
             originalMint: originalMintAddress,
             amount: amountBigInt,
+            maxFee: maxFee !== undefined ? BigInt(maxFee) : NO_FEE_LIMIT,
+            deadline: deadline ?? null,
+            referrerBalance: referrer ? await deriveReferrerBalancePda(originalMintAddress, address(referrer)) : undefined,
+            feeTreasury: feeTreasury ? address(feeTreasury) : undefined,
+            tokenProgram,
             userOriginalAccount: userOriginalAccountAddress,
             vault: vaultPda,
         });
@@ -232,7 +353,7 @@ export const wrapController = async (req: Request, res: Response) => {
 
 export const unwrapController = async (req: Request, res: Response) => {
     try {
-        const { payer, originalMint, amount, userOriginalAccount } = req.body;
+        const { payer, originalMint, amount, userOriginalAccount, maxFee, deadline, referrer, feeTreasury } = req.body;
 
         // Validate required fields
         if (!payer || !originalMint || !amount) {
@@ -245,10 +366,15 @@ export const unwrapController = async (req: Request, res: Response) => {
         if (!isValidSolanaAddress(originalMint)) return res.status(400).json({ error: "Invalid originalMint address format" });
         if (userOriginalAccount && !isValidSolanaAddress(userOriginalAccount)) return res.status(400).json({ error: "Invalid userOriginalAccount address format" });
         if (!isValidAmount(amount)) return res.status(400).json({ error: "Invalid amount: must be a positive integer" });
+        if (!isValidMaxFee(maxFee)) return res.status(400).json({ error: "Invalid maxFee: must be a non-negative integer" });
+        if (!isValidDeadline(deadline)) return res.status(400).json({ error: "Invalid deadline: must be a unix timestamp in seconds" });
+        if (referrer && !isValidSolanaAddress(referrer)) return res.status(400).json({ error: "Invalid referrer address format" });
+        if (feeTreasury && !isValidSolanaAddress(feeTreasury)) return res.status(400).json({ error: "Invalid feeTreasury address format" });
 
         const payerAddress = address(payer);
         const originalMintAddress = address(originalMint);
         const amountBigInt = BigInt(amount);
+        const tokenProgram = await getMintTokenProgram(originalMintAddress);
 
         let userOriginalAccountAddress: Address;
         if (userOriginalAccount) {
@@ -256,7 +382,9 @@ export const unwrapController = async (req: Request, res: Response) => {
         } else {
             const ata = getAssociatedTokenAddressSync(
                 new PublicKey(originalMintAddress),
-                new PublicKey(payerAddress)
+                new PublicKey(payerAddress),
+                false,
+                new PublicKey(tokenProgram)
             );
             userOriginalAccountAddress = address(ata.toBase58());
         }
@@ -286,6 +414,11 @@ export const unwrapController = async (req: Request, res: Response) => {
             user: createNoopSigner(payerAddress),
             originalMint: originalMintAddress,
             amount: amountBigInt,
+            maxFee: maxFee !== undefined ? BigInt(maxFee) : NO_FEE_LIMIT,
+            deadline: deadline ?? null,
+            referrerBalance: referrer ? await deriveReferrerBalancePda(originalMintAddress, address(referrer)) : undefined,
+            feeTreasury: feeTreasury ? address(feeTreasury) : undefined,
+            tokenProgram,
             userOriginalAccount: userOriginalAccountAddress,
             userWrappedAccount: address(userWrappedAccountAddress.toBase58()),
             vault: vaultPda,
@@ -322,16 +455,31 @@ export const unwrapController = async (req: Request, res: Response) => {
 export const configureConfidentialController = async (req: Request, res: Response) => {
     console.log("Configure confidential controller called");
     try {
-        const { payer, originalMint } = req.body;
+        const {
+            payer,
+            originalMint,
+            elgamalPubkey,
+            decryptableZeroBalance,
+            pubkeyValidityProof,
+            proofContext,
+        } = req.body;
         console.log("Request body:", req.body);
 
-        if (!payer || !originalMint) {
+        if (!payer || !originalMint || !elgamalPubkey || !decryptableZeroBalance) {
             return res.status(400).json({
                 error: "Missing required fields",
-                required: ["payer", "originalMint"]
+                required: ["payer", "originalMint", "elgamalPubkey", "decryptableZeroBalance"]
             });
         }
 
+        // The program rejects the configure instruction without a pubkey validity proof
+        if (!pubkeyValidityProof === !proofContext) {
+            return res.status(400).json({
+                error: "Provide exactly one of pubkeyValidityProof or proofContext"
+            });
+        }
+        if (proofContext && !isValidSolanaAddress(proofContext)) return res.status(400).json({ error: "Invalid proofContext address format" });
+
         const payerAddress = address(payer);
         const originalMintAddress = address(originalMint);
         console.log("Payer address:", payerAddress);
@@ -364,23 +512,34 @@ export const configureConfidentialController = async (req: Request, res: Respons
             payerAddress.toString(),
             wrappedMintPda.toString(),
             originalMintAddress.toString(),
-            userWrappedAccountAddress.toString()
+            userWrappedAccountAddress.toString(),
+            Buffer.from(elgamalPubkey, "base64"),
+            Buffer.from(decryptableZeroBalance, "base64"),
+            65536n,
+            1,
+            proofContext ?? null
         );
         console.log("Configure confidential instruction created.");
 
-        // Convert raw instruction to @solana/kit format
-        const ix = {
-            programAddress: address(ixRaw.programId),
-            accounts: ixRaw.keys.map(k => ({
+        // Convert raw instructions to @solana/kit format
+        const toKitInstruction = (raw: typeof ixRaw) => ({
+            programAddress: address(raw.programId),
+            accounts: raw.keys.map(k => ({
                 address: address(k.pubkey),
                 role: k.isWritable
                     ? (k.isSigner ? AccountRole.WRITABLE_SIGNER : AccountRole.WRITABLE)
                     : (k.isSigner ? AccountRole.READONLY_SIGNER : AccountRole.READONLY)
             })),
-            data: ixRaw.data
-        };
+            data: raw.data
+        });
+        const ix = toKitInstruction(ixRaw);
         console.log("Instruction Accounts (Config):", JSON.stringify(ix.accounts, null, 2));
 
+        // Without a context account the proof is verified in the next instruction
+        const proofIx = pubkeyValidityProof
+            ? toKitInstruction(createVerifyPubkeyValidityInstruction(Buffer.from(pubkeyValidityProof, "base64")))
+            : null;
+
         console.log("Getting latest blockhash for config...");
         const { value: latestBlockhash } = await rpc.getLatestBlockhash().send();
         console.log("Blockhash received:", latestBlockhash.blockhash);
@@ -391,12 +550,13 @@ export const configureConfidentialController = async (req: Request, res: Respons
             (m) => setTransactionMessageLifetimeUsingBlockhash(latestBlockhash, m),
             (m) => appendTransactionMessageInstruction(ix as any, m)
         );
+        const fullMessage = proofIx ? appendTransactionMessageInstruction(proofIx as any, message) : message;
 
         console.log("Instruction (Config):", JSON.stringify(ix, (key, value) =>
             typeof value === 'bigint' ? value.toString() :
                 value instanceof Uint8Array ? Array.from(value) : value
             , 2));
-        const compiledMessage = compileTransactionMessage(message);
+        const compiledMessage = compileTransactionMessage(fullMessage);
         console.log("Compiled Message (Config):", JSON.stringify(compiledMessage, (key, value) =>
             typeof value === 'bigint' ? value.toString() :
                 value instanceof Uint8Array ? Array.from(value) : value
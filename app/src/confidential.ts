@@ -74,11 +74,20 @@ export const isConfidentialTransferSupported = (): boolean => {
 /**
  * Creates a ConfigureConfidentialAccount instruction
  * 
+ * The pubkey validity proof for `elgamalPubkey` must be supplied either as a
+ * pre-verified context state account (`proofContext`) or as a ZK ElGamal proof
+ * program instruction at `proofInstructionOffset` relative to this one.
+ * 
  * @param programId - The C-SPL Wrapper program ID
  * @param user - The user's public key (signer)
  * @param wrappedMint - The wrapped Token-2022 mint
  * @param originalMint - The original SPL mint
  * @param userWrappedAccount - The user's wrapped token account
+ * @param elgamalPubkey - The user's ElGamal pubkey (32 bytes)
+ * @param decryptableZeroBalance - AE-encrypted zero balance (36 bytes)
+ * @param maximumPendingBalanceCreditCounter - Max pending credits before apply
+ * @param proofInstructionOffset - Offset of the proof instruction (ignored with proofContext)
+ * @param proofContext - Optional pubkey validity proof context state account
  * @param token2022Program - The Token-2022 program ID
  */
 export const createConfigureConfidentialInstruction = (
@@ -87,25 +96,26 @@ export const createConfigureConfidentialInstruction = (
     wrappedMint: string,
     originalMint: string,
     userWrappedAccount: string,
+    elgamalPubkey: Uint8Array, // 32 bytes
+    decryptableZeroBalance: Uint8Array, // 36 bytes
+    maximumPendingBalanceCreditCounter: bigint = 65536n,
+    proofInstructionOffset: number = 1,
+    proofContext: string | null = null,
     token2022Program: string = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
 ) => {
     const INSTRUCTIONS_SYSVAR = "Sysvar1nstructions1111111111111111111111111";
 
-    // Discriminator for configure_confidential_account
-    // In Anchor, this is usually calculated from "global:configure_confidential_account"
-    // But since we implemented it manually or via Anchor, let's use the Anchor discriminator.
-    // Wait, the program used the Anchor-generated discriminator because I used #[program].
-
-    // Default Anchor discriminator for "configure_confidential_account"
     // sha256("global:configure_confidential_account")[..8]
     const discriminator = Buffer.from([36, 212, 145, 231, 191, 23, 188, 119]);
 
-    // Data: elgamal_pubkey [u8; 32]
-    // For now, we'll pass a dummy pubkey if we're just triggering the instruction
-    // In a real scenario, this would be the user's ElGamal pubkey.
-    const dummyElGamalPubkey = Buffer.alloc(32);
-
-    const data = Buffer.concat([discriminator, dummyElGamalPubkey]);
+    // Data: elgamal_pubkey [u8; 32] | decryptable_zero_balance [u8; 36]
+    //       | maximum_pending_balance_credit_counter u64 | proof_instruction_offset i8
+    const data = Buffer.alloc(8 + 32 + 36 + 8 + 1);
+    discriminator.copy(data, 0);
+    Buffer.from(elgamalPubkey).copy(data, 8);
+    Buffer.from(decryptableZeroBalance).copy(data, 40);
+    data.writeBigUInt64LE(maximumPendingBalanceCreditCounter, 76);
+    data.writeInt8(proofInstructionOffset, 84);
 
     return {
         programId,
@@ -114,6 +124,8 @@ export const createConfigureConfidentialInstruction = (
             { pubkey: wrappedMint, isSigner: false, isWritable: false },
             { pubkey: originalMint, isSigner: false, isWritable: false },
             { pubkey: userWrappedAccount, isSigner: false, isWritable: true },
+            // Anchor optional account: the program ID stands in for `None`
+            { pubkey: proofContext ?? programId, isSigner: false, isWritable: false },
             { pubkey: token2022Program, isSigner: false, isWritable: false },
             { pubkey: INSTRUCTIONS_SYSVAR, isSigner: false, isWritable: false },
         ],
@@ -121,6 +133,36 @@ export const createConfigureConfidentialInstruction = (
     };
 };
 
+/**
+ * Creates a ZK ElGamal proof program VerifyPubkeyValidity instruction
+ * 
+ * Place it directly after ConfigureConfidentialAccount so the configure
+ * instruction can read it at `proofInstructionOffset` 1.
+ * 
+ * @param proofData - PubkeyValidityProofData: ElGamal pubkey (32) | proof (64)
+ */
+export const createVerifyPubkeyValidityInstruction = (
+    proofData: Uint8Array // 96 bytes
+) => {
+    const ZK_ELGAMAL_PROOF_PROGRAM = "ZkE1Gama1Proof11111111111111111111111111111";
+    // ProofInstruction::VerifyPubkeyValidity
+    const VERIFY_PUBKEY_VALIDITY = 4;
+
+    if (proofData.length !== 96) {
+        throw new Error("Pubkey validity proof data must be 96 bytes");
+    }
+
+    const data = Buffer.alloc(1 + 96);
+    data.writeUInt8(VERIFY_PUBKEY_VALIDITY, 0);
+    Buffer.from(proofData).copy(data, 1);
+
+    return {
+        programId: ZK_ELGAMAL_PROOF_PROGRAM,
+        keys: [] as { pubkey: string; isSigner: boolean; isWritable: boolean }[],
+        data,
+    };
+};
+
 /**
  * Creates an ApplyPendingBalance instruction
  */
@@ -10,8 +10,6 @@ import {
   combineCodec,
   fixDecoderSize,
   fixEncoderSize,
-  getAddressDecoder,
-  getAddressEncoder,
   getBytesDecoder,
   getBytesEncoder,
   getStructDecoder,
@@ -32,19 +30,22 @@ import {
   type WritableAccount,
 } from '@solana/kit';
 import { C_SPL_WRAPPER_PROGRAM_ADDRESS } from '../programs';
-import { getAccountMetaFactory, type ResolvedAccount } from '../shared';
+import {
+  getAccountMetaFactory,
+  type ResolvedAccount,
+} from '../shared';
 
-export const SET_AUTHORITY_DISCRIMINATOR = new Uint8Array([
-  133, 250, 37, 21, 110, 163, 26, 121,
+export const CANCEL_AUTHORITY_TRANSFER_DISCRIMINATOR = new Uint8Array([
+  94, 131, 125, 184, 183, 24, 125, 229,
 ]);
 
-export function getSetAuthorityDiscriminatorBytes() {
+export function getCancelAuthorityTransferDiscriminatorBytes() {
   return fixEncoderSize(getBytesEncoder(), 8).encode(
-    SET_AUTHORITY_DISCRIMINATOR
+    CANCEL_AUTHORITY_TRANSFER_DISCRIMINATOR
   );
 }
 
-export type SetAuthorityInstruction<
+export type CancelAuthorityTransferInstruction<
   TProgram extends string = typeof C_SPL_WRAPPER_PROGRAM_ADDRESS,
   TAccountWrapperConfig extends string | AccountMeta<string> = string,
   TAccountAuthority extends string | AccountMeta<string> = string,
@@ -64,57 +65,57 @@ export type SetAuthorityInstruction<
     ]
   >;
 
-export type SetAuthorityInstructionData = {
+export type CancelAuthorityTransferInstructionData = {
   discriminator: ReadonlyUint8Array;
-  newAuthority: Address;
 };
 
-export type SetAuthorityInstructionDataArgs = { newAuthority: Address };
+export type CancelAuthorityTransferInstructionDataArgs = {};
 
-export function getSetAuthorityInstructionDataEncoder(): FixedSizeEncoder<SetAuthorityInstructionDataArgs> {
+export function getCancelAuthorityTransferInstructionDataEncoder(): FixedSizeEncoder<CancelAuthorityTransferInstructionDataArgs> {
   return transformEncoder(
-    getStructEncoder([
-      ['discriminator', fixEncoderSize(getBytesEncoder(), 8)],
-      ['newAuthority', getAddressEncoder()],
-    ]),
-    (value) => ({ ...value, discriminator: SET_AUTHORITY_DISCRIMINATOR })
+    getStructEncoder([['discriminator', fixEncoderSize(getBytesEncoder(), 8)]]),
+    (value) => ({
+      ...value,
+      discriminator: CANCEL_AUTHORITY_TRANSFER_DISCRIMINATOR,
+    })
   );
 }
 
-export function getSetAuthorityInstructionDataDecoder(): FixedSizeDecoder<SetAuthorityInstructionData> {
+export function getCancelAuthorityTransferInstructionDataDecoder(): FixedSizeDecoder<CancelAuthorityTransferInstructionData> {
   return getStructDecoder([
     ['discriminator', fixDecoderSize(getBytesDecoder(), 8)],
-    ['newAuthority', getAddressDecoder()],
   ]);
 }
 
-export function getSetAuthorityInstructionDataCodec(): FixedSizeCodec<
-  SetAuthorityInstructionDataArgs,
-  SetAuthorityInstructionData
+export function getCancelAuthorityTransferInstructionDataCodec(): FixedSizeCodec<
+  CancelAuthorityTransferInstructionDataArgs,
+  CancelAuthorityTransferInstructionData
 > {
   return combineCodec(
-    getSetAuthorityInstructionDataEncoder(),
-    getSetAuthorityInstructionDataDecoder()
+    getCancelAuthorityTransferInstructionDataEncoder(),
+    getCancelAuthorityTransferInstructionDataDecoder()
   );
 }
 
-export type SetAuthorityInput<
+export type CancelAuthorityTransferInput<
   TAccountWrapperConfig extends string = string,
   TAccountAuthority extends string = string,
 > = {
   wrapperConfig: Address<TAccountWrapperConfig>;
   authority: TransactionSigner<TAccountAuthority>;
-  newAuthority: SetAuthorityInstructionDataArgs['newAuthority'];
 };
 
-export function getSetAuthorityInstruction<
+export function getCancelAuthorityTransferInstruction<
   TAccountWrapperConfig extends string,
   TAccountAuthority extends string,
   TProgramAddress extends Address = typeof C_SPL_WRAPPER_PROGRAM_ADDRESS,
 >(
-  input: SetAuthorityInput<TAccountWrapperConfig, TAccountAuthority>,
+  input: CancelAuthorityTransferInput<
+    TAccountWrapperConfig,
+    TAccountAuthority
+  >,
   config?: { programAddress?: TProgramAddress }
-): SetAuthorityInstruction<
+): CancelAuthorityTransferInstruction<
   TProgramAddress,
   TAccountWrapperConfig,
   TAccountAuthority
@@ -142,18 +143,18 @@ export function getSetAuthorityInstruction<
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.authority),
     ],
-    data: getSetAuthorityInstructionDataEncoder().encode(
-      args as SetAuthorityInstructionDataArgs
+    data: getCancelAuthorityTransferInstructionDataEncoder().encode(
+      args as CancelAuthorityTransferInstructionDataArgs
     ),
     programAddress,
-  } as SetAuthorityInstruction<
+  } as CancelAuthorityTransferInstruction<
     TProgramAddress,
     TAccountWrapperConfig,
     TAccountAuthority
   >);
 }
 
-export type ParsedSetAuthorityInstruction<
+export type ParsedCancelAuthorityTransferInstruction<
   TProgram extends string = typeof C_SPL_WRAPPER_PROGRAM_ADDRESS,
   TAccountMetas extends readonly AccountMeta[] = readonly AccountMeta[],
 > = {
@@ -162,17 +163,17 @@ export type ParsedSetAuthorityInstruction<
     wrapperConfig: TAccountMetas[0];
     authority: TAccountMetas[1];
   };
-  data: SetAuthorityInstructionData;
+  data: CancelAuthorityTransferInstructionData;
 };
 
-export function parseSetAuthorityInstruction<
+export function parseCancelAuthorityTransferInstruction<
   TProgram extends string,
   TAccountMetas extends readonly AccountMeta[],
 >(
   instruction: Instruction<TProgram> &
     InstructionWithAccounts<TAccountMetas> &
     InstructionWithData<ReadonlyUint8Array>
-): ParsedSetAuthorityInstruction<TProgram, TAccountMetas> {
+): ParsedCancelAuthorityTransferInstruction<TProgram, TAccountMetas> {
   if (instruction.accounts.length < 2) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
@@ -185,7 +186,12 @@ export function parseSetAuthorityInstruction<
   };
   return {
     programAddress: instruction.programAddress,
-    accounts: { wrapperConfig: getNextAccount(), authority: getNextAccount() },
-    data: getSetAuthorityInstructionDataDecoder().decode(instruction.data),
+    accounts: {
+      wrapperConfig: getNextAccount(),
+      authority: getNextAccount(),
+    },
+    data: getCancelAuthorityTransferInstructionDataDecoder().decode(
+      instruction.data
+    ),
   };
 }
@@ -6,10 +6,12 @@
  * @see https://github.com/codama-idl/codama
  */
 
+export * from './acceptAuthority';
+export * from './cancelAuthorityTransfer';
 export * from './freezeAccount';
 export * from './initialize';
 export * from './pause';
-export * from './setAuthority';
+export * from './proposeAuthority';
 export * from './setFees';
 export * from './thawAccount';
 export * from './unpause';
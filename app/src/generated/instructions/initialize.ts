@@ -7,13 +7,18 @@
  */
 
 import {
+  addDecoderSizePrefix,
+  addEncoderSizePrefix,
   combineCodec,
   fixDecoderSize,
   fixEncoderSize,
-  getAddressDecoder,
   getAddressEncoder,
+  getBooleanDecoder,
+  getBooleanEncoder,
   getBytesDecoder,
   getBytesEncoder,
+  getI64Decoder,
+  getI64Encoder,
   getOptionDecoder,
   getOptionEncoder,
   getProgramDerivedAddress,
@@ -21,6 +26,10 @@ import {
   getStructEncoder,
   getU16Decoder,
   getU16Encoder,
+  getU32Decoder,
+  getU32Encoder,
+  getUtf8Decoder,
+  getUtf8Encoder,
   transformEncoder,
   type AccountMeta,
   type AccountSignerMeta,
@@ -57,54 +66,65 @@ export function getInitializeDiscriminatorBytes() {
 export type InitializeInstruction<
   TProgram extends string = typeof C_SPL_WRAPPER_PROGRAM_ADDRESS,
   TAccountUser extends string | AccountMeta<string> = string,
+  TAccountProgramConfig extends string | AccountMeta<string> = string,
+  TAccountRegistryPage extends string | AccountMeta<string> = string,
   TAccountOriginalMint extends string | AccountMeta<string> = string,
   TAccountWrapperConfig extends string | AccountMeta<string> = string,
   TAccountWrapperStats extends string | AccountMeta<string> = string,
   TAccountWrappedMint extends string | AccountMeta<string> = string,
   TAccountVault extends string | AccountMeta<string> = string,
+  TAccountOriginalMetadata extends string | AccountMeta<string> = string,
   TAccountSystemProgram extends string | AccountMeta<string> =
-  '11111111111111111111111111111111',
-  TAccountTokenProgram extends string | AccountMeta<string> =
-  'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+    '11111111111111111111111111111111',
+  TAccountTokenProgram extends string | AccountMeta<string> = string,
   TAccountToken2022Program extends string | AccountMeta<string> =
-  'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
+    'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
   TAccountRent extends string | AccountMeta<string> =
-  'SysvarRent111111111111111111111111111111111',
+    'SysvarRent111111111111111111111111111111111',
   TRemainingAccounts extends readonly AccountMeta<string>[] = [],
 > = Instruction<TProgram> &
   InstructionWithData<ReadonlyUint8Array> &
   InstructionWithAccounts<
     [
       TAccountUser extends string
-      ? WritableSignerAccount<TAccountUser> & AccountSignerMeta<TAccountUser>
-      : TAccountUser,
+        ? WritableSignerAccount<TAccountUser> & AccountSignerMeta<TAccountUser>
+        : TAccountUser,
+      TAccountProgramConfig extends string
+        ? WritableAccount<TAccountProgramConfig>
+        : TAccountProgramConfig,
+      TAccountRegistryPage extends string
+        ? WritableAccount<TAccountRegistryPage>
+        : TAccountRegistryPage,
       TAccountOriginalMint extends string
-      ? ReadonlyAccount<TAccountOriginalMint>
-      : TAccountOriginalMint,
+        ? ReadonlyAccount<TAccountOriginalMint>
+        : TAccountOriginalMint,
       TAccountWrapperConfig extends string
-      ? WritableAccount<TAccountWrapperConfig>
-      : TAccountWrapperConfig,
+        ? WritableAccount<TAccountWrapperConfig>
+        : TAccountWrapperConfig,
       TAccountWrapperStats extends string
-      ? WritableAccount<TAccountWrapperStats>
-      : TAccountWrapperStats,
+        ? WritableAccount<TAccountWrapperStats>
+        : TAccountWrapperStats,
       TAccountWrappedMint extends string
-      ? WritableAccount<TAccountWrappedMint>
-      : TAccountWrappedMint,
+        ? WritableAccount<TAccountWrappedMint>
+        : TAccountWrappedMint,
       TAccountVault extends string
-      ? WritableAccount<TAccountVault>
-      : TAccountVault,
+        ? WritableAccount<TAccountVault>
+        : TAccountVault,
+      TAccountOriginalMetadata extends string
+        ? ReadonlyAccount<TAccountOriginalMetadata>
+        : TAccountOriginalMetadata,
       TAccountSystemProgram extends string
-      ? ReadonlyAccount<TAccountSystemProgram>
-      : TAccountSystemProgram,
+        ? ReadonlyAccount<TAccountSystemProgram>
+        : TAccountSystemProgram,
       TAccountTokenProgram extends string
-      ? ReadonlyAccount<TAccountTokenProgram>
-      : TAccountTokenProgram,
+        ? ReadonlyAccount<TAccountTokenProgram>
+        : TAccountTokenProgram,
       TAccountToken2022Program extends string
-      ? ReadonlyAccount<TAccountToken2022Program>
-      : TAccountToken2022Program,
+        ? ReadonlyAccount<TAccountToken2022Program>
+        : TAccountToken2022Program,
       TAccountRent extends string
-      ? ReadonlyAccount<TAccountRent>
-      : TAccountRent,
+        ? ReadonlyAccount<TAccountRent>
+        : TAccountRent,
       ...TRemainingAccounts,
     ]
   >;
@@ -114,12 +134,18 @@ export type InitializeInstructionData = {
   wrapFeeBps: number;
   unwrapFeeBps: number;
   auditorElgamalPubkey: Option<ReadonlyUint8Array>;
+  autoApproveNewAccounts: boolean;
+  metadataPrefix: string;
+  timelockDelay: bigint;
 };
 
 export type InitializeInstructionDataArgs = {
   wrapFeeBps: number;
   unwrapFeeBps: number;
   auditorElgamalPubkey: OptionOrNullable<ReadonlyUint8Array>;
+  autoApproveNewAccounts: boolean;
+  metadataPrefix: string;
+  timelockDelay: number | bigint;
 };
 
 export function getInitializeInstructionDataEncoder(): Encoder<InitializeInstructionDataArgs> {
@@ -132,6 +158,12 @@ export function getInitializeInstructionDataEncoder(): Encoder<InitializeInstruc
         'auditorElgamalPubkey',
         getOptionEncoder(fixEncoderSize(getBytesEncoder(), 32)),
       ],
+      ['autoApproveNewAccounts', getBooleanEncoder()],
+      [
+        'metadataPrefix',
+        addEncoderSizePrefix(getUtf8Encoder(), getU32Encoder()),
+      ],
+      ['timelockDelay', getI64Encoder()],
     ]),
     (value) => ({ ...value, discriminator: INITIALIZE_DISCRIMINATOR })
   );
@@ -146,6 +178,9 @@ export function getInitializeInstructionDataDecoder(): Decoder<InitializeInstruc
       'auditorElgamalPubkey',
       getOptionDecoder(fixDecoderSize(getBytesDecoder(), 32)),
     ],
+    ['autoApproveNewAccounts', getBooleanDecoder()],
+    ['metadataPrefix', addDecoderSizePrefix(getUtf8Decoder(), getU32Decoder())],
+    ['timelockDelay', getI64Decoder()],
   ]);
 }
 
@@ -161,38 +196,52 @@ export function getInitializeInstructionDataCodec(): Codec<
 
 export type InitializeAsyncInput<
   TAccountUser extends string = string,
+  TAccountProgramConfig extends string = string,
+  TAccountRegistryPage extends string = string,
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountVault extends string = string,
+  TAccountOriginalMetadata extends string = string,
   TAccountSystemProgram extends string = string,
   TAccountTokenProgram extends string = string,
   TAccountToken2022Program extends string = string,
   TAccountRent extends string = string,
 > = {
   user: TransactionSigner<TAccountUser>;
+  programConfig?: Address<TAccountProgramConfig>;
+  registryPage: Address<TAccountRegistryPage>;
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig?: Address<TAccountWrapperConfig>;
   wrapperStats?: Address<TAccountWrapperStats>;
   wrappedMint?: Address<TAccountWrappedMint>;
   vault?: Address<TAccountVault>;
+  /** Original mint's Metaplex metadata, if it has one */
+  originalMetadata?: Address<TAccountOriginalMetadata>;
   systemProgram?: Address<TAccountSystemProgram>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  /** Token program that owns the original mint (SPL Token or Token-2022) */
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   rent?: Address<TAccountRent>;
   wrapFeeBps: InitializeInstructionDataArgs['wrapFeeBps'];
   unwrapFeeBps: InitializeInstructionDataArgs['unwrapFeeBps'];
   auditorElgamalPubkey: InitializeInstructionDataArgs['auditorElgamalPubkey'];
+  autoApproveNewAccounts: InitializeInstructionDataArgs['autoApproveNewAccounts'];
+  metadataPrefix: InitializeInstructionDataArgs['metadataPrefix'];
+  timelockDelay: InitializeInstructionDataArgs['timelockDelay'];
 };
 
 export async function getInitializeInstructionAsync<
   TAccountUser extends string,
+  TAccountProgramConfig extends string,
+  TAccountRegistryPage extends string,
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
   TAccountWrappedMint extends string,
   TAccountVault extends string,
+  TAccountOriginalMetadata extends string,
   TAccountSystemProgram extends string,
   TAccountTokenProgram extends string,
   TAccountToken2022Program extends string,
@@ -201,11 +250,14 @@ export async function getInitializeInstructionAsync<
 >(
   input: InitializeAsyncInput<
     TAccountUser,
+    TAccountProgramConfig,
+    TAccountRegistryPage,
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
     TAccountWrappedMint,
     TAccountVault,
+    TAccountOriginalMetadata,
     TAccountSystemProgram,
     TAccountTokenProgram,
     TAccountToken2022Program,
@@ -216,11 +268,14 @@ export async function getInitializeInstructionAsync<
   InitializeInstruction<
     TProgramAddress,
     TAccountUser,
+    TAccountProgramConfig,
+    TAccountRegistryPage,
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
     TAccountWrappedMint,
     TAccountVault,
+    TAccountOriginalMetadata,
     TAccountSystemProgram,
     TAccountTokenProgram,
     TAccountToken2022Program,
@@ -234,11 +289,17 @@ export async function getInitializeInstructionAsync<
   // Original accounts.
   const originalAccounts = {
     user: { value: input.user ?? null, isWritable: true },
+    programConfig: { value: input.programConfig ?? null, isWritable: true },
+    registryPage: { value: input.registryPage ?? null, isWritable: true },
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: true },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     vault: { value: input.vault ?? null, isWritable: true },
+    originalMetadata: {
+      value: input.originalMetadata ?? null,
+      isWritable: false,
+    },
     systemProgram: { value: input.systemProgram ?? null, isWritable: false },
     tokenProgram: { value: input.tokenProgram ?? null, isWritable: false },
     token2022Program: {
@@ -256,6 +317,18 @@ export async function getInitializeInstructionAsync<
   const args = { ...input };
 
   // Resolve default values.
+  if (!accounts.programConfig.value) {
+    accounts.programConfig.value = await getProgramDerivedAddress({
+      programAddress,
+      seeds: [
+        getBytesEncoder().encode(
+          new Uint8Array([
+            112, 114, 111, 103, 114, 97, 109, 95, 99, 111, 110, 102, 105, 103,
+          ])
+        ),
+      ],
+    });
+  }
   if (!accounts.wrapperConfig.value) {
     accounts.wrapperConfig.value = await getProgramDerivedAddress({
       programAddress,
@@ -296,10 +369,6 @@ export async function getInitializeInstructionAsync<
     accounts.systemProgram.value =
       '11111111111111111111111111111111' as Address<'11111111111111111111111111111111'>;
   }
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.token2022Program.value) {
     accounts.token2022Program.value =
       'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb' as Address<'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'>;
@@ -313,11 +382,14 @@ export async function getInitializeInstructionAsync<
   return Object.freeze({
     accounts: [
       getAccountMeta(accounts.user),
+      getAccountMeta(accounts.programConfig),
+      getAccountMeta(accounts.registryPage),
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.vault),
+      getAccountMeta(accounts.originalMetadata),
       getAccountMeta(accounts.systemProgram),
       getAccountMeta(accounts.tokenProgram),
       getAccountMeta(accounts.token2022Program),
@@ -330,11 +402,14 @@ export async function getInitializeInstructionAsync<
   } as InitializeInstruction<
     TProgramAddress,
     TAccountUser,
+    TAccountProgramConfig,
+    TAccountRegistryPage,
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
     TAccountWrappedMint,
     TAccountVault,
+    TAccountOriginalMetadata,
     TAccountSystemProgram,
     TAccountTokenProgram,
     TAccountToken2022Program,
@@ -344,38 +419,52 @@ export async function getInitializeInstructionAsync<
 
 export type InitializeInput<
   TAccountUser extends string = string,
+  TAccountProgramConfig extends string = string,
+  TAccountRegistryPage extends string = string,
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountVault extends string = string,
+  TAccountOriginalMetadata extends string = string,
   TAccountSystemProgram extends string = string,
   TAccountTokenProgram extends string = string,
   TAccountToken2022Program extends string = string,
   TAccountRent extends string = string,
 > = {
   user: TransactionSigner<TAccountUser>;
+  programConfig: Address<TAccountProgramConfig>;
+  registryPage: Address<TAccountRegistryPage>;
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig: Address<TAccountWrapperConfig>;
   wrapperStats: Address<TAccountWrapperStats>;
   wrappedMint: Address<TAccountWrappedMint>;
   vault: Address<TAccountVault>;
+  /** Original mint's Metaplex metadata, if it has one */
+  originalMetadata?: Address<TAccountOriginalMetadata>;
   systemProgram?: Address<TAccountSystemProgram>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  /** Token program that owns the original mint (SPL Token or Token-2022) */
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   rent?: Address<TAccountRent>;
   wrapFeeBps: InitializeInstructionDataArgs['wrapFeeBps'];
   unwrapFeeBps: InitializeInstructionDataArgs['unwrapFeeBps'];
   auditorElgamalPubkey: InitializeInstructionDataArgs['auditorElgamalPubkey'];
+  autoApproveNewAccounts: InitializeInstructionDataArgs['autoApproveNewAccounts'];
+  metadataPrefix: InitializeInstructionDataArgs['metadataPrefix'];
+  timelockDelay: InitializeInstructionDataArgs['timelockDelay'];
 };
 
 export function getInitializeInstruction<
   TAccountUser extends string,
+  TAccountProgramConfig extends string,
+  TAccountRegistryPage extends string,
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
   TAccountWrappedMint extends string,
   TAccountVault extends string,
+  TAccountOriginalMetadata extends string,
   TAccountSystemProgram extends string,
   TAccountTokenProgram extends string,
   TAccountToken2022Program extends string,
@@ -384,11 +473,14 @@ export function getInitializeInstruction<
 >(
   input: InitializeInput<
     TAccountUser,
+    TAccountProgramConfig,
+    TAccountRegistryPage,
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
     TAccountWrappedMint,
     TAccountVault,
+    TAccountOriginalMetadata,
     TAccountSystemProgram,
     TAccountTokenProgram,
     TAccountToken2022Program,
@@ -398,11 +490,14 @@ export function getInitializeInstruction<
 ): InitializeInstruction<
   TProgramAddress,
   TAccountUser,
+  TAccountProgramConfig,
+  TAccountRegistryPage,
   TAccountOriginalMint,
   TAccountWrapperConfig,
   TAccountWrapperStats,
   TAccountWrappedMint,
   TAccountVault,
+  TAccountOriginalMetadata,
   TAccountSystemProgram,
   TAccountTokenProgram,
   TAccountToken2022Program,
@@ -415,11 +510,17 @@ export function getInitializeInstruction<
   // Original accounts.
   const originalAccounts = {
     user: { value: input.user ?? null, isWritable: true },
+    programConfig: { value: input.programConfig ?? null, isWritable: true },
+    registryPage: { value: input.registryPage ?? null, isWritable: true },
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: true },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     vault: { value: input.vault ?? null, isWritable: true },
+    originalMetadata: {
+      value: input.originalMetadata ?? null,
+      isWritable: false,
+    },
     systemProgram: { value: input.systemProgram ?? null, isWritable: false },
     tokenProgram: { value: input.tokenProgram ?? null, isWritable: false },
     token2022Program: {
@@ -441,10 +542,6 @@ export function getInitializeInstruction<
     accounts.systemProgram.value =
       '11111111111111111111111111111111' as Address<'11111111111111111111111111111111'>;
   }
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.token2022Program.value) {
     accounts.token2022Program.value =
       'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb' as Address<'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'>;
@@ -458,11 +555,14 @@ export function getInitializeInstruction<
   return Object.freeze({
     accounts: [
       getAccountMeta(accounts.user),
+      getAccountMeta(accounts.programConfig),
+      getAccountMeta(accounts.registryPage),
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.vault),
+      getAccountMeta(accounts.originalMetadata),
       getAccountMeta(accounts.systemProgram),
       getAccountMeta(accounts.tokenProgram),
       getAccountMeta(accounts.token2022Program),
@@ -475,11 +575,14 @@ export function getInitializeInstruction<
   } as InitializeInstruction<
     TProgramAddress,
     TAccountUser,
+    TAccountProgramConfig,
+    TAccountRegistryPage,
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
     TAccountWrappedMint,
     TAccountVault,
+    TAccountOriginalMetadata,
     TAccountSystemProgram,
     TAccountTokenProgram,
     TAccountToken2022Program,
@@ -494,15 +597,18 @@ export type ParsedInitializeInstruction<
   programAddress: Address<TProgram>;
   accounts: {
     user: TAccountMetas[0];
-    originalMint: TAccountMetas[1];
-    wrapperConfig: TAccountMetas[2];
-    wrapperStats: TAccountMetas[3];
-    wrappedMint: TAccountMetas[4];
-    vault: TAccountMetas[5];
-    systemProgram: TAccountMetas[6];
-    tokenProgram: TAccountMetas[7];
-    token2022Program: TAccountMetas[8];
-    rent: TAccountMetas[9];
+    programConfig: TAccountMetas[1];
+    registryPage: TAccountMetas[2];
+    originalMint: TAccountMetas[3];
+    wrapperConfig: TAccountMetas[4];
+    wrapperStats: TAccountMetas[5];
+    wrappedMint: TAccountMetas[6];
+    vault: TAccountMetas[7];
+    originalMetadata?: TAccountMetas[8] | undefined;
+    systemProgram: TAccountMetas[9];
+    tokenProgram: TAccountMetas[10];
+    token2022Program: TAccountMetas[11];
+    rent: TAccountMetas[12];
   };
   data: InitializeInstructionData;
 };
@@ -515,7 +621,7 @@ export function parseInitializeInstruction<
     InstructionWithAccounts<TAccountMetas> &
     InstructionWithData<ReadonlyUint8Array>
 ): ParsedInitializeInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 10) {
+  if (instruction.accounts.length < 13) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
   }
@@ -525,15 +631,24 @@ export function parseInitializeInstruction<
     accountIndex += 1;
     return accountMeta;
   };
+  const getNextOptionalAccount = () => {
+    const accountMeta = getNextAccount();
+    return accountMeta.address === C_SPL_WRAPPER_PROGRAM_ADDRESS
+      ? undefined
+      : accountMeta;
+  };
   return {
     programAddress: instruction.programAddress,
     accounts: {
       user: getNextAccount(),
+      programConfig: getNextAccount(),
+      registryPage: getNextAccount(),
       originalMint: getNextAccount(),
       wrapperConfig: getNextAccount(),
       wrapperStats: getNextAccount(),
       wrappedMint: getNextAccount(),
       vault: getNextAccount(),
+      originalMetadata: getNextOptionalAccount(),
       systemProgram: getNextAccount(),
       tokenProgram: getNextAccount(),
       token2022Program: getNextAccount(),
@@ -13,6 +13,10 @@ import {
   getAddressEncoder,
   getBytesDecoder,
   getBytesEncoder,
+  getI64Decoder,
+  getI64Encoder,
+  getOptionDecoder,
+  getOptionEncoder,
   getProgramDerivedAddress,
   getStructDecoder,
   getStructEncoder,
@@ -22,12 +26,14 @@ import {
   type AccountMeta,
   type AccountSignerMeta,
   type Address,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
+  type Codec,
+  type Decoder,
+  type Encoder,
   type Instruction,
   type InstructionWithAccounts,
   type InstructionWithData,
+  type Option,
+  type OptionOrNullable,
   type ReadonlyAccount,
   type ReadonlyUint8Array,
   type TransactionSigner,
@@ -55,12 +61,14 @@ export type UnwrapInstruction<
   TAccountOriginalMint extends string | AccountMeta<string> = string,
   TAccountWrapperConfig extends string | AccountMeta<string> = string,
   TAccountWrapperStats extends string | AccountMeta<string> = string,
+  TAccountUserVolume extends string | AccountMeta<string> = string,
+  TAccountFeeTreasury extends string | AccountMeta<string> = string,
+  TAccountReferrerBalance extends string | AccountMeta<string> = string,
   TAccountWrappedMint extends string | AccountMeta<string> = string,
   TAccountUserOriginalAccount extends string | AccountMeta<string> = string,
   TAccountVault extends string | AccountMeta<string> = string,
   TAccountUserWrappedAccount extends string | AccountMeta<string> = string,
-  TAccountTokenProgram extends string | AccountMeta<string> =
-    'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+  TAccountTokenProgram extends string | AccountMeta<string> = string,
   TAccountToken2022Program extends string | AccountMeta<string> =
     'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
   TAccountSystemProgram extends string | AccountMeta<string> =
@@ -84,6 +92,15 @@ export type UnwrapInstruction<
       TAccountWrapperStats extends string
         ? WritableAccount<TAccountWrapperStats>
         : TAccountWrapperStats,
+      TAccountUserVolume extends string
+        ? WritableAccount<TAccountUserVolume>
+        : TAccountUserVolume,
+      TAccountFeeTreasury extends string
+        ? WritableAccount<TAccountFeeTreasury>
+        : TAccountFeeTreasury,
+      TAccountReferrerBalance extends string
+        ? WritableAccount<TAccountReferrerBalance>
+        : TAccountReferrerBalance,
       TAccountWrappedMint extends string
         ? WritableAccount<TAccountWrappedMint>
         : TAccountWrappedMint,
@@ -115,28 +132,38 @@ export type UnwrapInstruction<
 export type UnwrapInstructionData = {
   discriminator: ReadonlyUint8Array;
   amount: bigint;
+  maxFee: bigint;
+  deadline: Option<bigint>;
 };
 
-export type UnwrapInstructionDataArgs = { amount: number | bigint };
+export type UnwrapInstructionDataArgs = {
+  amount: number | bigint;
+  maxFee: number | bigint;
+  deadline: OptionOrNullable<number | bigint>;
+};
 
-export function getUnwrapInstructionDataEncoder(): FixedSizeEncoder<UnwrapInstructionDataArgs> {
+export function getUnwrapInstructionDataEncoder(): Encoder<UnwrapInstructionDataArgs> {
   return transformEncoder(
     getStructEncoder([
       ['discriminator', fixEncoderSize(getBytesEncoder(), 8)],
       ['amount', getU64Encoder()],
+      ['maxFee', getU64Encoder()],
+      ['deadline', getOptionEncoder(getI64Encoder())],
     ]),
     (value) => ({ ...value, discriminator: UNWRAP_DISCRIMINATOR })
   );
 }
 
-export function getUnwrapInstructionDataDecoder(): FixedSizeDecoder<UnwrapInstructionData> {
+export function getUnwrapInstructionDataDecoder(): Decoder<UnwrapInstructionData> {
   return getStructDecoder([
     ['discriminator', fixDecoderSize(getBytesDecoder(), 8)],
     ['amount', getU64Decoder()],
+    ['maxFee', getU64Decoder()],
+    ['deadline', getOptionDecoder(getI64Decoder())],
   ]);
 }
 
-export function getUnwrapInstructionDataCodec(): FixedSizeCodec<
+export function getUnwrapInstructionDataCodec(): Codec<
   UnwrapInstructionDataArgs,
   UnwrapInstructionData
 > {
@@ -151,6 +178,9 @@ export type UnwrapAsyncInput<
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
+  TAccountUserVolume extends string = string,
+  TAccountFeeTreasury extends string = string,
+  TAccountReferrerBalance extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountUserOriginalAccount extends string = string,
   TAccountVault extends string = string,
@@ -164,15 +194,22 @@ export type UnwrapAsyncInput<
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig?: Address<TAccountWrapperConfig>;
   wrapperStats?: Address<TAccountWrapperStats>;
+  userVolume?: Address<TAccountUserVolume>;
+  /** Receives fees in wrapped tokens when `fee_currency` is `Wrapped` */
+  feeTreasury?: Address<TAccountFeeTreasury>;
+  /** Optional referrer credited with `referral_share_bps` of the fee */
+  referrerBalance?: Address<TAccountReferrerBalance>;
   wrappedMint?: Address<TAccountWrappedMint>;
   userOriginalAccount: Address<TAccountUserOriginalAccount>;
   vault: Address<TAccountVault>;
   userWrappedAccount: Address<TAccountUserWrappedAccount>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   systemProgram?: Address<TAccountSystemProgram>;
   associatedTokenProgram?: Address<TAccountAssociatedTokenProgram>;
   amount: UnwrapInstructionDataArgs['amount'];
+  maxFee: UnwrapInstructionDataArgs['maxFee'];
+  deadline: UnwrapInstructionDataArgs['deadline'];
 };
 
 export async function getUnwrapInstructionAsync<
@@ -180,6 +217,9 @@ export async function getUnwrapInstructionAsync<
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
+  TAccountUserVolume extends string,
+  TAccountFeeTreasury extends string,
+  TAccountReferrerBalance extends string,
   TAccountWrappedMint extends string,
   TAccountUserOriginalAccount extends string,
   TAccountVault extends string,
@@ -195,6 +235,9 @@ export async function getUnwrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -212,6 +255,9 @@ export async function getUnwrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -232,6 +278,9 @@ export async function getUnwrapInstructionAsync<
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: false },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
+    userVolume: { value: input.userVolume ?? null, isWritable: true },
+    feeTreasury: { value: input.feeTreasury ?? null, isWritable: true },
+    referrerBalance: { value: input.referrerBalance ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     userOriginalAccount: {
       value: input.userOriginalAccount ?? null,
@@ -280,6 +329,20 @@ export async function getUnwrapInstructionAsync<
       ],
     });
   }
+  if (!accounts.userVolume.value) {
+    accounts.userVolume.value = await getProgramDerivedAddress({
+      programAddress,
+      seeds: [
+        getBytesEncoder().encode(
+          new Uint8Array([
+            117, 115, 101, 114, 95, 118, 111, 108, 117, 109, 101,
+          ])
+        ),
+        getAddressEncoder().encode(expectAddress(accounts.wrapperConfig.value)),
+        getAddressEncoder().encode(expectAddress(accounts.user.value)),
+      ],
+    });
+  }
   if (!accounts.wrappedMint.value) {
     accounts.wrappedMint.value = await getProgramDerivedAddress({
       programAddress,
@@ -289,10 +352,6 @@ export async function getUnwrapInstructionAsync<
       ],
     });
   }
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.token2022Program.value) {
     accounts.token2022Program.value =
       'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb' as Address<'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'>;
@@ -313,6 +372,9 @@ export async function getUnwrapInstructionAsync<
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
+      getAccountMeta(accounts.userVolume),
+      getAccountMeta(accounts.feeTreasury),
+      getAccountMeta(accounts.referrerBalance),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.userOriginalAccount),
       getAccountMeta(accounts.vault),
@@ -332,6 +394,9 @@ export async function getUnwrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -348,6 +413,9 @@ export type UnwrapInput<
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
+  TAccountUserVolume extends string = string,
+  TAccountFeeTreasury extends string = string,
+  TAccountReferrerBalance extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountUserOriginalAccount extends string = string,
   TAccountVault extends string = string,
@@ -361,15 +429,22 @@ export type UnwrapInput<
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig: Address<TAccountWrapperConfig>;
   wrapperStats: Address<TAccountWrapperStats>;
+  userVolume: Address<TAccountUserVolume>;
+  /** Receives fees in wrapped tokens when `fee_currency` is `Wrapped` */
+  feeTreasury?: Address<TAccountFeeTreasury>;
+  /** Optional referrer credited with `referral_share_bps` of the fee */
+  referrerBalance?: Address<TAccountReferrerBalance>;
   wrappedMint: Address<TAccountWrappedMint>;
   userOriginalAccount: Address<TAccountUserOriginalAccount>;
   vault: Address<TAccountVault>;
   userWrappedAccount: Address<TAccountUserWrappedAccount>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   systemProgram?: Address<TAccountSystemProgram>;
   associatedTokenProgram?: Address<TAccountAssociatedTokenProgram>;
   amount: UnwrapInstructionDataArgs['amount'];
+  maxFee: UnwrapInstructionDataArgs['maxFee'];
+  deadline: UnwrapInstructionDataArgs['deadline'];
 };
 
 export function getUnwrapInstruction<
@@ -377,6 +452,9 @@ export function getUnwrapInstruction<
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
+  TAccountUserVolume extends string,
+  TAccountFeeTreasury extends string,
+  TAccountReferrerBalance extends string,
   TAccountWrappedMint extends string,
   TAccountUserOriginalAccount extends string,
   TAccountVault extends string,
@@ -392,6 +470,9 @@ export function getUnwrapInstruction<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -408,6 +489,9 @@ export function getUnwrapInstruction<
   TAccountOriginalMint,
   TAccountWrapperConfig,
   TAccountWrapperStats,
+  TAccountUserVolume,
+  TAccountFeeTreasury,
+  TAccountReferrerBalance,
   TAccountWrappedMint,
   TAccountUserOriginalAccount,
   TAccountVault,
@@ -427,6 +511,9 @@ export function getUnwrapInstruction<
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: false },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
+    userVolume: { value: input.userVolume ?? null, isWritable: true },
+    feeTreasury: { value: input.feeTreasury ?? null, isWritable: true },
+    referrerBalance: { value: input.referrerBalance ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     userOriginalAccount: {
       value: input.userOriginalAccount ?? null,
@@ -457,10 +544,6 @@ export function getUnwrapInstruction<
   const args = { ...input };
 
   // Resolve default values.
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.token2022Program.value) {
     accounts.token2022Program.value =
       'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb' as Address<'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'>;
@@ -481,6 +564,9 @@ export function getUnwrapInstruction<
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
+      getAccountMeta(accounts.userVolume),
+      getAccountMeta(accounts.feeTreasury),
+      getAccountMeta(accounts.referrerBalance),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.userOriginalAccount),
       getAccountMeta(accounts.vault),
@@ -500,6 +586,9 @@ export function getUnwrapInstruction<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -521,14 +610,17 @@ export type ParsedUnwrapInstruction<
     originalMint: TAccountMetas[1];
     wrapperConfig: TAccountMetas[2];
     wrapperStats: TAccountMetas[3];
-    wrappedMint: TAccountMetas[4];
-    userOriginalAccount: TAccountMetas[5];
-    vault: TAccountMetas[6];
-    userWrappedAccount: TAccountMetas[7];
-    tokenProgram: TAccountMetas[8];
-    token2022Program: TAccountMetas[9];
-    systemProgram: TAccountMetas[10];
-    associatedTokenProgram: TAccountMetas[11];
+    userVolume: TAccountMetas[4];
+    feeTreasury?: TAccountMetas[5] | undefined;
+    referrerBalance?: TAccountMetas[6] | undefined;
+    wrappedMint: TAccountMetas[7];
+    userOriginalAccount: TAccountMetas[8];
+    vault: TAccountMetas[9];
+    userWrappedAccount: TAccountMetas[10];
+    tokenProgram: TAccountMetas[11];
+    token2022Program: TAccountMetas[12];
+    systemProgram: TAccountMetas[13];
+    associatedTokenProgram: TAccountMetas[14];
   };
   data: UnwrapInstructionData;
 };
@@ -541,7 +633,7 @@ export function parseUnwrapInstruction<
     InstructionWithAccounts<TAccountMetas> &
     InstructionWithData<ReadonlyUint8Array>
 ): ParsedUnwrapInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 12) {
+  if (instruction.accounts.length < 15) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
   }
@@ -551,6 +643,12 @@ export function parseUnwrapInstruction<
     accountIndex += 1;
     return accountMeta;
   };
+  const getNextOptionalAccount = () => {
+    const accountMeta = getNextAccount();
+    return accountMeta.address === C_SPL_WRAPPER_PROGRAM_ADDRESS
+      ? undefined
+      : accountMeta;
+  };
   return {
     programAddress: instruction.programAddress,
     accounts: {
@@ -558,6 +656,9 @@ export function parseUnwrapInstruction<
       originalMint: getNextAccount(),
       wrapperConfig: getNextAccount(),
       wrapperStats: getNextAccount(),
+      userVolume: getNextAccount(),
+      feeTreasury: getNextOptionalAccount(),
+      referrerBalance: getNextOptionalAccount(),
       wrappedMint: getNextAccount(),
       userOriginalAccount: getNextAccount(),
       vault: getNextAccount(),
@@ -13,6 +13,10 @@ import {
   getAddressEncoder,
   getBytesDecoder,
   getBytesEncoder,
+  getI64Decoder,
+  getI64Encoder,
+  getOptionDecoder,
+  getOptionEncoder,
   getProgramDerivedAddress,
   getStructDecoder,
   getStructEncoder,
@@ -22,12 +26,14 @@ import {
   type AccountMeta,
   type AccountSignerMeta,
   type Address,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
+  type Codec,
+  type Decoder,
+  type Encoder,
   type Instruction,
   type InstructionWithAccounts,
   type InstructionWithData,
+  type Option,
+  type OptionOrNullable,
   type ReadonlyAccount,
   type ReadonlyUint8Array,
   type TransactionSigner,
@@ -55,12 +61,14 @@ export type WrapInstruction<
   TAccountOriginalMint extends string | AccountMeta<string> = string,
   TAccountWrapperConfig extends string | AccountMeta<string> = string,
   TAccountWrapperStats extends string | AccountMeta<string> = string,
+  TAccountUserVolume extends string | AccountMeta<string> = string,
+  TAccountFeeTreasury extends string | AccountMeta<string> = string,
+  TAccountReferrerBalance extends string | AccountMeta<string> = string,
   TAccountWrappedMint extends string | AccountMeta<string> = string,
   TAccountUserOriginalAccount extends string | AccountMeta<string> = string,
   TAccountVault extends string | AccountMeta<string> = string,
   TAccountUserWrappedAccount extends string | AccountMeta<string> = string,
-  TAccountTokenProgram extends string | AccountMeta<string> =
-    'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+  TAccountTokenProgram extends string | AccountMeta<string> = string,
   TAccountToken2022Program extends string | AccountMeta<string> =
     'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
   TAccountSystemProgram extends string | AccountMeta<string> =
@@ -84,6 +92,15 @@ export type WrapInstruction<
       TAccountWrapperStats extends string
         ? WritableAccount<TAccountWrapperStats>
         : TAccountWrapperStats,
+      TAccountUserVolume extends string
+        ? WritableAccount<TAccountUserVolume>
+        : TAccountUserVolume,
+      TAccountFeeTreasury extends string
+        ? WritableAccount<TAccountFeeTreasury>
+        : TAccountFeeTreasury,
+      TAccountReferrerBalance extends string
+        ? WritableAccount<TAccountReferrerBalance>
+        : TAccountReferrerBalance,
       TAccountWrappedMint extends string
         ? WritableAccount<TAccountWrappedMint>
         : TAccountWrappedMint,
@@ -115,28 +132,38 @@ export type WrapInstruction<
 export type WrapInstructionData = {
   discriminator: ReadonlyUint8Array;
   amount: bigint;
+  maxFee: bigint;
+  deadline: Option<bigint>;
 };
 
-export type WrapInstructionDataArgs = { amount: number | bigint };
+export type WrapInstructionDataArgs = {
+  amount: number | bigint;
+  maxFee: number | bigint;
+  deadline: OptionOrNullable<number | bigint>;
+};
 
-export function getWrapInstructionDataEncoder(): FixedSizeEncoder<WrapInstructionDataArgs> {
+export function getWrapInstructionDataEncoder(): Encoder<WrapInstructionDataArgs> {
   return transformEncoder(
     getStructEncoder([
       ['discriminator', fixEncoderSize(getBytesEncoder(), 8)],
       ['amount', getU64Encoder()],
+      ['maxFee', getU64Encoder()],
+      ['deadline', getOptionEncoder(getI64Encoder())],
     ]),
     (value) => ({ ...value, discriminator: WRAP_DISCRIMINATOR })
   );
 }
 
-export function getWrapInstructionDataDecoder(): FixedSizeDecoder<WrapInstructionData> {
+export function getWrapInstructionDataDecoder(): Decoder<WrapInstructionData> {
   return getStructDecoder([
     ['discriminator', fixDecoderSize(getBytesDecoder(), 8)],
     ['amount', getU64Decoder()],
+    ['maxFee', getU64Decoder()],
+    ['deadline', getOptionDecoder(getI64Decoder())],
   ]);
 }
 
-export function getWrapInstructionDataCodec(): FixedSizeCodec<
+export function getWrapInstructionDataCodec(): Codec<
   WrapInstructionDataArgs,
   WrapInstructionData
 > {
@@ -151,6 +178,9 @@ export type WrapAsyncInput<
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
+  TAccountUserVolume extends string = string,
+  TAccountFeeTreasury extends string = string,
+  TAccountReferrerBalance extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountUserOriginalAccount extends string = string,
   TAccountVault extends string = string,
@@ -164,15 +194,22 @@ export type WrapAsyncInput<
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig?: Address<TAccountWrapperConfig>;
   wrapperStats?: Address<TAccountWrapperStats>;
+  userVolume?: Address<TAccountUserVolume>;
+  /** Receives fees in wrapped tokens when `fee_currency` is `Wrapped` */
+  feeTreasury?: Address<TAccountFeeTreasury>;
+  /** Optional referrer credited with `referral_share_bps` of the fee */
+  referrerBalance?: Address<TAccountReferrerBalance>;
   wrappedMint?: Address<TAccountWrappedMint>;
   userOriginalAccount: Address<TAccountUserOriginalAccount>;
   vault: Address<TAccountVault>;
   userWrappedAccount?: Address<TAccountUserWrappedAccount>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   systemProgram?: Address<TAccountSystemProgram>;
   associatedTokenProgram?: Address<TAccountAssociatedTokenProgram>;
   amount: WrapInstructionDataArgs['amount'];
+  maxFee: WrapInstructionDataArgs['maxFee'];
+  deadline: WrapInstructionDataArgs['deadline'];
 };
 
 export async function getWrapInstructionAsync<
@@ -180,6 +217,9 @@ export async function getWrapInstructionAsync<
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
+  TAccountUserVolume extends string,
+  TAccountFeeTreasury extends string,
+  TAccountReferrerBalance extends string,
   TAccountWrappedMint extends string,
   TAccountUserOriginalAccount extends string,
   TAccountVault extends string,
@@ -195,6 +235,9 @@ export async function getWrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -212,6 +255,9 @@ export async function getWrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -232,6 +278,9 @@ export async function getWrapInstructionAsync<
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: false },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
+    userVolume: { value: input.userVolume ?? null, isWritable: true },
+    feeTreasury: { value: input.feeTreasury ?? null, isWritable: true },
+    referrerBalance: { value: input.referrerBalance ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     userOriginalAccount: {
       value: input.userOriginalAccount ?? null,
@@ -280,6 +329,20 @@ export async function getWrapInstructionAsync<
       ],
     });
   }
+  if (!accounts.userVolume.value) {
+    accounts.userVolume.value = await getProgramDerivedAddress({
+      programAddress,
+      seeds: [
+        getBytesEncoder().encode(
+          new Uint8Array([
+            117, 115, 101, 114, 95, 118, 111, 108, 117, 109, 101,
+          ])
+        ),
+        getAddressEncoder().encode(expectAddress(accounts.wrapperConfig.value)),
+        getAddressEncoder().encode(expectAddress(accounts.user.value)),
+      ],
+    });
+  }
   if (!accounts.wrappedMint.value) {
     accounts.wrappedMint.value = await getProgramDerivedAddress({
       programAddress,
@@ -306,10 +369,6 @@ export async function getWrapInstructionAsync<
       ],
     });
   }
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.systemProgram.value) {
     accounts.systemProgram.value =
       '11111111111111111111111111111111' as Address<'11111111111111111111111111111111'>;
@@ -326,6 +385,9 @@ export async function getWrapInstructionAsync<
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
+      getAccountMeta(accounts.userVolume),
+      getAccountMeta(accounts.feeTreasury),
+      getAccountMeta(accounts.referrerBalance),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.userOriginalAccount),
       getAccountMeta(accounts.vault),
@@ -345,6 +407,9 @@ export async function getWrapInstructionAsync<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -361,6 +426,9 @@ export type WrapInput<
   TAccountOriginalMint extends string = string,
   TAccountWrapperConfig extends string = string,
   TAccountWrapperStats extends string = string,
+  TAccountUserVolume extends string = string,
+  TAccountFeeTreasury extends string = string,
+  TAccountReferrerBalance extends string = string,
   TAccountWrappedMint extends string = string,
   TAccountUserOriginalAccount extends string = string,
   TAccountVault extends string = string,
@@ -374,15 +442,22 @@ export type WrapInput<
   originalMint: Address<TAccountOriginalMint>;
   wrapperConfig: Address<TAccountWrapperConfig>;
   wrapperStats: Address<TAccountWrapperStats>;
+  userVolume: Address<TAccountUserVolume>;
+  /** Receives fees in wrapped tokens when `fee_currency` is `Wrapped` */
+  feeTreasury?: Address<TAccountFeeTreasury>;
+  /** Optional referrer credited with `referral_share_bps` of the fee */
+  referrerBalance?: Address<TAccountReferrerBalance>;
   wrappedMint: Address<TAccountWrappedMint>;
   userOriginalAccount: Address<TAccountUserOriginalAccount>;
   vault: Address<TAccountVault>;
   userWrappedAccount: Address<TAccountUserWrappedAccount>;
-  tokenProgram?: Address<TAccountTokenProgram>;
+  tokenProgram: Address<TAccountTokenProgram>;
   token2022Program?: Address<TAccountToken2022Program>;
   systemProgram?: Address<TAccountSystemProgram>;
   associatedTokenProgram?: Address<TAccountAssociatedTokenProgram>;
   amount: WrapInstructionDataArgs['amount'];
+  maxFee: WrapInstructionDataArgs['maxFee'];
+  deadline: WrapInstructionDataArgs['deadline'];
 };
 
 export function getWrapInstruction<
@@ -390,6 +465,9 @@ export function getWrapInstruction<
   TAccountOriginalMint extends string,
   TAccountWrapperConfig extends string,
   TAccountWrapperStats extends string,
+  TAccountUserVolume extends string,
+  TAccountFeeTreasury extends string,
+  TAccountReferrerBalance extends string,
   TAccountWrappedMint extends string,
   TAccountUserOriginalAccount extends string,
   TAccountVault extends string,
@@ -405,6 +483,9 @@ export function getWrapInstruction<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -421,6 +502,9 @@ export function getWrapInstruction<
   TAccountOriginalMint,
   TAccountWrapperConfig,
   TAccountWrapperStats,
+  TAccountUserVolume,
+  TAccountFeeTreasury,
+  TAccountReferrerBalance,
   TAccountWrappedMint,
   TAccountUserOriginalAccount,
   TAccountVault,
@@ -440,6 +524,9 @@ export function getWrapInstruction<
     originalMint: { value: input.originalMint ?? null, isWritable: false },
     wrapperConfig: { value: input.wrapperConfig ?? null, isWritable: false },
     wrapperStats: { value: input.wrapperStats ?? null, isWritable: true },
+    userVolume: { value: input.userVolume ?? null, isWritable: true },
+    feeTreasury: { value: input.feeTreasury ?? null, isWritable: true },
+    referrerBalance: { value: input.referrerBalance ?? null, isWritable: true },
     wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
     userOriginalAccount: {
       value: input.userOriginalAccount ?? null,
@@ -474,10 +561,6 @@ export function getWrapInstruction<
     accounts.token2022Program.value =
       'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb' as Address<'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'>;
   }
-  if (!accounts.tokenProgram.value) {
-    accounts.tokenProgram.value =
-      'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA' as Address<'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'>;
-  }
   if (!accounts.systemProgram.value) {
     accounts.systemProgram.value =
       '11111111111111111111111111111111' as Address<'11111111111111111111111111111111'>;
@@ -494,6 +577,9 @@ export function getWrapInstruction<
       getAccountMeta(accounts.originalMint),
       getAccountMeta(accounts.wrapperConfig),
       getAccountMeta(accounts.wrapperStats),
+      getAccountMeta(accounts.userVolume),
+      getAccountMeta(accounts.feeTreasury),
+      getAccountMeta(accounts.referrerBalance),
       getAccountMeta(accounts.wrappedMint),
       getAccountMeta(accounts.userOriginalAccount),
       getAccountMeta(accounts.vault),
@@ -513,6 +599,9 @@ export function getWrapInstruction<
     TAccountOriginalMint,
     TAccountWrapperConfig,
     TAccountWrapperStats,
+    TAccountUserVolume,
+    TAccountFeeTreasury,
+    TAccountReferrerBalance,
     TAccountWrappedMint,
     TAccountUserOriginalAccount,
     TAccountVault,
@@ -534,14 +623,17 @@ export type ParsedWrapInstruction<
     originalMint: TAccountMetas[1];
     wrapperConfig: TAccountMetas[2];
     wrapperStats: TAccountMetas[3];
-    wrappedMint: TAccountMetas[4];
-    userOriginalAccount: TAccountMetas[5];
-    vault: TAccountMetas[6];
-    userWrappedAccount: TAccountMetas[7];
-    tokenProgram: TAccountMetas[8];
-    token2022Program: TAccountMetas[9];
-    systemProgram: TAccountMetas[10];
-    associatedTokenProgram: TAccountMetas[11];
+    userVolume: TAccountMetas[4];
+    feeTreasury?: TAccountMetas[5] | undefined;
+    referrerBalance?: TAccountMetas[6] | undefined;
+    wrappedMint: TAccountMetas[7];
+    userOriginalAccount: TAccountMetas[8];
+    vault: TAccountMetas[9];
+    userWrappedAccount: TAccountMetas[10];
+    tokenProgram: TAccountMetas[11];
+    token2022Program: TAccountMetas[12];
+    systemProgram: TAccountMetas[13];
+    associatedTokenProgram: TAccountMetas[14];
   };
   data: WrapInstructionData;
 };
@@ -554,7 +646,7 @@ export function parseWrapInstruction<
     InstructionWithAccounts<TAccountMetas> &
     InstructionWithData<ReadonlyUint8Array>
 ): ParsedWrapInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 12) {
+  if (instruction.accounts.length < 15) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
   }
@@ -564,6 +656,12 @@ export function parseWrapInstruction<
     accountIndex += 1;
     return accountMeta;
   };
+  const getNextOptionalAccount = () => {
+    const accountMeta = getNextAccount();
+    return accountMeta.address === C_SPL_WRAPPER_PROGRAM_ADDRESS
+      ? undefined
+      : accountMeta;
+  };
   return {
     programAddress: instruction.programAddress,
     accounts: {
@@ -571,6 +669,9 @@ export function parseWrapInstruction<
       originalMint: getNextAccount(),
       wrapperConfig: getNextAccount(),
       wrapperStats: getNextAccount(),
+      userVolume: getNextAccount(),
+      feeTreasury: getNextOptionalAccount(),
+      referrerBalance: getNextOptionalAccount(),
       wrappedMint: getNextAccount(),
       userOriginalAccount: getNextAccount(),
       vault: getNextAccount(),
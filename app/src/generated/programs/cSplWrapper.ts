@@ -14,10 +14,12 @@ import {
   type ReadonlyUint8Array,
 } from '@solana/kit';
 import {
+  type ParsedAcceptAuthorityInstruction,
+  type ParsedCancelAuthorityTransferInstruction,
   type ParsedFreezeAccountInstruction,
   type ParsedInitializeInstruction,
   type ParsedPauseInstruction,
-  type ParsedSetAuthorityInstruction,
+  type ParsedProposeAuthorityInstruction,
   type ParsedSetFeesInstruction,
   type ParsedThawAccountInstruction,
   type ParsedUnpauseInstruction,
@@ -66,10 +68,12 @@ export function identifyCSplWrapperAccount(
 }
 
 export enum CSplWrapperInstruction {
+  AcceptAuthority,
+  CancelAuthorityTransfer,
   FreezeAccount,
   Initialize,
   Pause,
-  SetAuthority,
+  ProposeAuthority,
   SetFees,
   ThawAccount,
   Unpause,
@@ -82,6 +86,28 @@ export function identifyCSplWrapperInstruction(
   instruction: { data: ReadonlyUint8Array } | ReadonlyUint8Array
 ): CSplWrapperInstruction {
   const data = 'data' in instruction ? instruction.data : instruction;
+  if (
+    containsBytes(
+      data,
+      fixEncoderSize(getBytesEncoder(), 8).encode(
+        new Uint8Array([107, 86, 198, 91, 33, 12, 107, 160])
+      ),
+      0
+    )
+  ) {
+    return CSplWrapperInstruction.AcceptAuthority;
+  }
+  if (
+    containsBytes(
+      data,
+      fixEncoderSize(getBytesEncoder(), 8).encode(
+        new Uint8Array([94, 131, 125, 184, 183, 24, 125, 229])
+      ),
+      0
+    )
+  ) {
+    return CSplWrapperInstruction.CancelAuthorityTransfer;
+  }
   if (
     containsBytes(
       data,
@@ -119,12 +145,12 @@ export function identifyCSplWrapperInstruction(
     containsBytes(
       data,
       fixEncoderSize(getBytesEncoder(), 8).encode(
-        new Uint8Array([133, 250, 37, 21, 110, 163, 26, 121])
+        new Uint8Array([20, 148, 236, 198, 76, 119, 99, 142])
       ),
       0
     )
   ) {
-    return CSplWrapperInstruction.SetAuthority;
+    return CSplWrapperInstruction.ProposeAuthority;
   }
   if (
     containsBytes(
@@ -200,6 +226,12 @@ export function identifyCSplWrapperInstruction(
 export type ParsedCSplWrapperInstruction<
   TProgram extends string = 'D3FaNQVD8NZC6CFT1AS8Rq2G26iAGZ19CgLJXNMfGAjY',
 > =
+  | ({
+      instructionType: CSplWrapperInstruction.AcceptAuthority;
+    } & ParsedAcceptAuthorityInstruction<TProgram>)
+  | ({
+      instructionType: CSplWrapperInstruction.CancelAuthorityTransfer;
+    } & ParsedCancelAuthorityTransferInstruction<TProgram>)
   | ({
       instructionType: CSplWrapperInstruction.FreezeAccount;
     } & ParsedFreezeAccountInstruction<TProgram>)
@@ -210,8 +242,8 @@ export type ParsedCSplWrapperInstruction<
       instructionType: CSplWrapperInstruction.Pause;
     } & ParsedPauseInstruction<TProgram>)
   | ({
-      instructionType: CSplWrapperInstruction.SetAuthority;
-    } & ParsedSetAuthorityInstruction<TProgram>)
+      instructionType: CSplWrapperInstruction.ProposeAuthority;
+    } & ParsedProposeAuthorityInstruction<TProgram>)
   | ({
       instructionType: CSplWrapperInstruction.SetFees;
     } & ParsedSetFeesInstruction<TProgram>)
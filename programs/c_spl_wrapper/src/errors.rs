@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum WrapperError {
-    #[msg("Wrapper is paused")]
-    WrapperPaused,
+    #[msg("Wrapping is paused")]
+    WrapPaused,
     #[msg("Amount cannot be zero")]
     ZeroAmount,
     #[msg("Unauthorized access")]
@@ -20,4 +20,56 @@ pub enum WrapperError {
     FeeCalculationError,
     #[msg("Insufficient vault balance")]
     InsufficientVaultBalance,
+    #[msg("Wrapper is not a native SOL wrapper")]
+    NotNativeMint,
+    #[msg("Metadata prefix too long")]
+    MetadataPrefixTooLong,
+    #[msg("Invalid or missing zero-knowledge proof")]
+    InvalidProof,
+    #[msg("Proof does not match the provided ElGamal pubkey")]
+    ElGamalPubkeyMismatch,
+    #[msg("Unwrapping is paused")]
+    UnwrapPaused,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Role is not assigned")]
+    RoleNotAssigned,
+    #[msg("Timelock delay is out of range")]
+    InvalidTimelockDelay,
+    #[msg("Timelocked action is not yet executable")]
+    TimelockNotExpired,
+    #[msg("Only the factory admin or the original mint authority can initialize")]
+    UnauthorizedInitializer,
+    #[msg("Invalid fee schedule: min or flat fee exceeds max fee")]
+    InvalidFeeSchedule,
+    #[msg("Amount does not cover the fee")]
+    AmountBelowFee,
+    #[msg("Fee tiers must be ascending by volume, at most 4, with discounts up to 100%")]
+    InvalidFeeTiers,
+    #[msg("Fee split needs 1-4 distinct recipients with shares summing to 100%")]
+    InvalidFeeSplit,
+    #[msg("Recipient accounts do not match the fee split")]
+    FeeRecipientMismatch,
+    #[msg("Referral share cannot exceed 100%")]
+    InvalidReferralShare,
+    #[msg("Amount exceeds accrued fees")]
+    InsufficientAccruedFees,
+    #[msg("Fee treasury account is missing or does not match the config")]
+    InvalidFeeTreasury,
+    #[msg("Fee exceeds the caller's max_fee")]
+    FeeExceedsLimit,
+    #[msg("Transaction deadline has passed")]
+    DeadlineExpired,
+    #[msg("Required input exceeds the caller's max_amount_in")]
+    MaxInputExceeded,
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    #[msg("Fees are paid out through the fee split; use distribute_fees")]
+    FeeSplitConfigured,
+    #[msg("Amount produced differs from the requested exact output")]
+    ExactOutputMismatch,
+    #[msg("User volume account is missing or does not match the action")]
+    InvalidUserVolume,
+    #[msg("No fee split is configured")]
+    FeeSplitNotConfigured,
 }
@@ -1,5 +1,24 @@
 use anchor_lang::prelude::*;
 
+use crate::fees::{FeeCurrency, FeeRecipient, FeeSchedule, FeeTier};
+use crate::state::{AdminAction, InitializerKind, PauseDirection, Role};
+
+#[event]
+pub struct FactoryAdminUpdatedEvent {
+    pub old_factory_admin: Pubkey,
+    pub new_factory_admin: Pubkey,
+}
+
+#[event]
+pub struct WrapperInitializedEvent {
+    pub wrapper_config: Pubkey,
+    pub original_mint: Pubkey,
+    pub wrapped_mint: Pubkey,
+    pub authority: Pubkey,
+    pub initialized_by: InitializerKind,
+    pub registry_index: u64,
+}
+
 #[event]
 pub struct WrapEvent {
     pub user: Pubkey,
@@ -7,6 +26,9 @@ pub struct WrapEvent {
     pub wrapped_mint: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub transfer_fee: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
     pub timestamp: i64,
 }
 
@@ -17,13 +39,18 @@ pub struct UnwrapEvent {
     pub wrapped_mint: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub transfer_fee: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct PauseEvent {
     pub authority: Pubkey,
+    pub direction: PauseDirection,
     pub is_paused: bool,
+    pub transfers_affected: bool,
 }
 
 #[event]
@@ -33,6 +60,37 @@ pub struct FeesUpdatedEvent {
     pub unwrap_fee_bps: u16,
 }
 
+#[event]
+pub struct FeeScheduleUpdatedEvent {
+    pub authority: Pubkey,
+    pub fee_schedule: FeeSchedule,
+}
+
+#[event]
+pub struct FeeTiersUpdatedEvent {
+    pub authority: Pubkey,
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[event]
+pub struct FeeExemptionUpdatedEvent {
+    pub authority: Pubkey,
+    pub user: Pubkey,
+    pub exempt: bool,
+}
+
+#[event]
+pub struct AuthorityProposedEvent {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
 #[event]
 pub struct AuthorityUpdatedEvent {
     pub old_authority: Pubkey,
@@ -46,8 +104,132 @@ pub struct FeesWithdrawnEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct FeeCurrencyUpdatedEvent {
+    pub authority: Pubkey,
+    pub fee_currency: FeeCurrency,
+    pub fee_treasury: Pubkey,
+}
+
+#[event]
+pub struct ReferralShareUpdatedEvent {
+    pub authority: Pubkey,
+    pub referral_share_bps: u16,
+}
+
+#[event]
+pub struct ReferrerRegisteredEvent {
+    pub authority: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeesClaimedEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSplitUpdatedEvent {
+    pub authority: Pubkey,
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+pub struct FeesDistributedEvent {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SurplusSweptEvent {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub surplus: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AccountFrozenEvent {
     pub account: Pubkey,
     pub frozen: bool,
 }
+
+#[event]
+pub struct AuditorUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_auditor: Option<[u8; 32]>,
+    pub new_auditor: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct AutoApproveUpdatedEvent {
+    pub authority: Pubkey,
+    pub auto_approve_new_accounts: bool,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct RoleGrantedEvent {
+    pub authority: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+    pub previous: Pubkey,
+}
+
+#[event]
+pub struct RoleRevokedEvent {
+    pub authority: Pubkey,
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct ConfidentialAccountApprovedEvent {
+    pub approver: Pubkey,
+    pub account: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionQueuedEvent {
+    pub wrapper_config: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ActionExecutedEvent {
+    pub wrapper_config: Pubkey,
+    pub id: u64,
+    pub executor: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct ActionCancelledEvent {
+    pub wrapper_config: Pubkey,
+    pub id: u64,
+    pub cancelled_by: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct TimelockDelayUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_timelock_delay: i64,
+    pub new_timelock_delay: i64,
+}
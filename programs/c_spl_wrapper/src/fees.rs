@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::WrapperError;
+use crate::state::{UserVolume, WrapperConfig};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_FEE_BPS: u16 = 1000; // 10%
+pub const MAX_FEE_TIERS: usize = 4;
+pub const MAX_FEE_RECIPIENTS: usize = 4;
+
+/// How the bps component of a fee is rounded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeRounding {
+    Down,
+    Up,
+}
+
+/// Which token fees are kept in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeCurrency {
+    Original,   // Left in the vault as original tokens
+    Wrapped,    // Paid to the fee treasury as wrapped tokens
+}
+
+/// Fee parameters shared by wrap and unwrap; the bps rate is set per direction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub flat_fee: u64,          // Added to every wrap/unwrap
+    pub min_fee: u64,           // Floor on the total fee
+    pub max_fee: u64,           // Ceiling on the total fee (0 = none)
+    pub rounding: FeeRounding,
+}
+
+impl FeeSchedule {
+    // 8 flat_fee + 8 min_fee + 8 max_fee + 1 rounding
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+
+    /// No flat component or bounds, rounded down: a plain `amount * bps / 10_000`
+    pub const DEFAULT: Self = Self {
+        flat_fee: 0,
+        min_fee: 0,
+        max_fee: 0,
+        rounding: FeeRounding::Down,
+    };
+
+    /// Checked once when the schedule is queued and again when it is applied, so the
+    /// bounds never have to be second-guessed per wrap/unwrap
+    pub fn validate(&self) -> Result<()> {
+        if self.max_fee > 0 {
+            require!(self.min_fee <= self.max_fee, WrapperError::InvalidFeeSchedule);
+            require!(self.flat_fee <= self.max_fee, WrapperError::InvalidFeeSchedule);
+        }
+        Ok(())
+    }
+}
+
+/// Discount on the bps rate for users whose cumulative volume reaches `min_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_volume: u64,        // In original-token base units
+    pub discount_bps: u16,      // Share of the bps rate waived (10_000 = free)
+}
+
+impl FeeTier {
+    // 8 min_volume + 2 discount_bps
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Tiers must be strictly ascending by volume, with discounts of at most 100%
+pub fn validate_fee_tiers(tiers: &[FeeTier]) -> Result<()> {
+    require!(tiers.len() <= MAX_FEE_TIERS, WrapperError::InvalidFeeTiers);
+    require!(
+        tiers.iter().all(|t| t.discount_bps as u64 <= BPS_DENOMINATOR),
+        WrapperError::InvalidFeeTiers
+    );
+    require!(
+        tiers.windows(2).all(|w| w[0].min_volume < w[1].min_volume),
+        WrapperError::InvalidFeeTiers
+    );
+    Ok(())
+}
+
+/// One payee of `distribute_fees`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub token_account: Pubkey,  // Original-mint token account that receives the share
+    pub share_bps: u16,         // Share of each distribution (all shares sum to 10_000)
+}
+
+impl FeeRecipient {
+    // 32 token_account + 2 share_bps
+    pub const LEN: usize = 32 + 2;
+}
+
+/// 1..=4 distinct recipients, each with a non-zero share, summing to exactly 100%
+pub fn validate_fee_split(recipients: &[FeeRecipient]) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+        WrapperError::InvalidFeeSplit
+    );
+    require!(recipients.iter().all(|r| r.share_bps > 0), WrapperError::InvalidFeeSplit);
+    for (i, r) in recipients.iter().enumerate() {
+        require!(
+            recipients[i + 1..].iter().all(|o| o.token_account != r.token_account),
+            WrapperError::InvalidFeeSplit
+        );
+    }
+    let total: u64 = recipients.iter().map(|r| r.share_bps as u64).sum();
+    require!(total == BPS_DENOMINATOR, WrapperError::InvalidFeeSplit);
+    Ok(())
+}
+
+/// Each recipient's cut of `total`, rounded down; the last recipient also takes the dust
+pub fn split_amount(total: u64, recipients: &[FeeRecipient]) -> Result<Vec<u64>> {
+    let mut amounts = Vec::with_capacity(recipients.len());
+    let mut remaining = total;
+    for (i, r) in recipients.iter().enumerate() {
+        let amount = if i + 1 == recipients.len() {
+            remaining
+        } else {
+            let share = (total as u128 * r.share_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+            remaining = remaining.checked_sub(share).ok_or(WrapperError::FeeCalculationError)?;
+            share
+        };
+        amounts.push(amount);
+    }
+    Ok(amounts)
+}
+
+/// Fee charged to a particular user: zero if fee-exempt, otherwise `compute_fee` at
+/// their tier-discounted rate.
+pub fn user_fee(
+    amount: u64,
+    base_bps: u16,
+    config: &WrapperConfig,
+    user_volume: &UserVolume,
+) -> Result<u64> {
+    match user_bps(base_bps, config, user_volume)? {
+        Some(bps) => compute_fee(amount, bps, &config.fee_schedule),
+        None => Ok(0),
+    }
+}
+
+/// Inverse of `user_fee`: the amount this user must put in for `net` to come out
+pub fn user_gross_for_net(
+    net: u64,
+    base_bps: u16,
+    config: &WrapperConfig,
+    user_volume: &UserVolume,
+) -> Result<u64> {
+    match user_bps(base_bps, config, user_volume)? {
+        Some(bps) => gross_for_net(net, bps, &config.fee_schedule),
+        None => Ok(net),
+    }
+}
+
+/// `base_bps` reduced by the highest tier the user's prior volume reaches;
+/// `None` if the user is fee-exempt.
+fn user_bps(base_bps: u16, config: &WrapperConfig, user_volume: &UserVolume) -> Result<Option<u16>> {
+    if user_volume.fee_exempt {
+        return Ok(None);
+    }
+
+    let discount_bps = config
+        .fee_tiers
+        .iter()
+        .rev()
+        .find(|t| user_volume.cumulative_volume >= t.min_volume)
+        .map_or(0, |t| t.discount_bps as u64);
+    let bps = (base_bps as u64)
+        .checked_mul(BPS_DENOMINATOR.saturating_sub(discount_bps))
+        .ok_or(WrapperError::FeeCalculationError)?
+        / BPS_DENOMINATOR;
+    require!(bps <= MAX_FEE_BPS as u64, WrapperError::FeeTooHigh);
+
+    Ok(Some(bps as u16))
+}
+
+/// Referrer's cut of an already computed `fee`, rounded down
+pub fn referral_share(fee: u64, referral_share_bps: u16) -> Result<u64> {
+    let share = (fee as u128)
+        .checked_mul(referral_share_bps as u128)
+        .ok_or(WrapperError::FeeCalculationError)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(share).map_err(|_| WrapperError::FeeCalculationError.into())
+}
+
+/// Fee charged on `amount`: `amount * bps / 10_000` (rounded per the schedule) plus the
+/// flat fee, clamped to `[min_fee, max_fee]`. Fails if it would consume the whole amount.
+pub fn compute_fee(amount: u64, bps: u16, schedule: &FeeSchedule) -> Result<u64> {
+    let fee = bounded_fee(amount, bps, schedule)?;
+    require!(fee < amount, WrapperError::AmountBelowFee);
+    Ok(fee)
+}
+
+/// Smallest amount whose net after `compute_fee` is exactly `net`, found by binary search
+/// over the forward calculation so rounding and bounds match it. The fee grows by at most
+/// one per unit of amount (bps <= 100%), so the net hits every value on the way up.
+pub fn gross_for_net(net: u64, bps: u16, schedule: &FeeSchedule) -> Result<u64> {
+    let net_of = |amount: u64| -> Result<u64> {
+        Ok(amount.saturating_sub(bounded_fee(amount, bps, schedule)?))
+    };
+    require!(net_of(u64::MAX)? >= net, WrapperError::FeeCalculationError);
+
+    let (mut low, mut high) = (net, u64::MAX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if net_of(mid)? >= net {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
+/// `compute_fee` without the check that the fee leaves something of `amount`
+fn bounded_fee(amount: u64, bps: u16, schedule: &FeeSchedule) -> Result<u64> {
+    require!(bps <= MAX_FEE_BPS, WrapperError::FeeTooHigh);
+    let numerator = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(WrapperError::FeeCalculationError)?;
+    let denominator = BPS_DENOMINATOR as u128;
+    let bps_fee = match schedule.rounding {
+        FeeRounding::Down => numerator / denominator,
+        FeeRounding::Up => numerator.div_ceil(denominator),
+    };
+
+    let mut fee = u64::try_from(bps_fee)
+        .map_err(|_| WrapperError::FeeCalculationError)?
+        .checked_add(schedule.flat_fee)
+        .ok_or(WrapperError::FeeCalculationError)?
+        .max(schedule.min_fee);
+    if schedule.max_fee > 0 {
+        fee = fee.min(schedule.max_fee);
+    }
+    Ok(fee)
+}
@@ -2,75 +2,719 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
     program::invoke,
     program::invoke_signed,
+    program_option::COption,
     system_instruction,
     instruction::{AccountMeta, Instruction},
+    sysvar::instructions::get_instruction_relative,
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Token, Transfer},
-    token_interface::{Mint, TokenAccount},
+    token::{spl_token::native_mint::ID as NATIVE_MINT, Token},
+    metadata::{MetadataAccount, ID as METADATA_PROGRAM_ID},
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked},
     token_2022::Token2022,
+    token_2022_extensions::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::{
+            self,
+            state::{Field, TokenMetadata},
+        },
+    },
 };
 use anchor_spl::token_2022::spl_token_2022::{
     extension::{
         confidential_transfer,
+        metadata_pointer,
+        pausable,
+        transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensions,
         ExtensionType,
+        StateWithExtensions,
+    },
+    solana_zk_sdk::{
+        encryption::pod::elgamal::PodElGamalPubkey,
+        zk_elgamal_proof_program::{
+            self,
+            instruction::ProofInstruction,
+            proof_data::{ProofType, PubkeyValidityProofContext, PubkeyValidityProofData},
+            state::ProofContextState,
+        },
     },
     state::Mint as MintState,
 };
+use spl_token_confidential_transfer_proof_extraction::instruction::{ProofData, ProofLocation};
+use std::num::NonZeroI8;
 
 pub mod state;
 pub mod errors;
 pub mod events;
+pub mod fees;
 
 use state::*;
 use errors::*;
 use events::*;
+use fees::{FeeCurrency, FeeRecipient, FeeSchedule};
 
 declare_id!("D3FaNQVD8NZC6CFT1AS8Rq2G26iAGZ19CgLJXNMfGAjY");
 
+/// The original mint's `TransferFeeConfig`, if it is a Token-2022 mint that has one
+fn original_mint_fee_config(original_mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *original_mint.owner != Token2022::id() {
+        return Ok(None);
+    }
+    let data = original_mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Transfer fee the original mint withholds on an outbound transfer of `amount`.
+/// Zero for classic SPL mints and Token-2022 mints without `TransferFeeConfig`.
+fn original_mint_transfer_fee(original_mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match original_mint_fee_config(original_mint)? {
+        Some(fee_config) => Ok(fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(WrapperError::FeeCalculationError)?),
+        None => Ok(0),
+    }
+}
+
+/// Amount to send so that `received` arrives after the original mint's transfer fee.
+/// Equal to `received` for classic SPL mints and Token-2022 mints without `TransferFeeConfig`.
+fn original_mint_amount_for_received(original_mint: &AccountInfo, received: u64) -> Result<u64> {
+    match original_mint_fee_config(original_mint)? {
+        Some(fee_config) => {
+            let transfer_fee = fee_config
+                .calculate_inverse_epoch_fee(Clock::get()?.epoch, received)
+                .ok_or(WrapperError::FeeCalculationError)?;
+            Ok(received.checked_add(transfer_fee).ok_or(WrapperError::Overflow)?)
+        }
+        None => Ok(received),
+    }
+}
+
+/// Name, symbol and URI for the wrapped mint, derived from the original's Metaplex metadata.
+/// Metaplex pads these strings with NULs, so they are trimmed before prefixing.
+/// Without metadata, a shortened original mint address keeps wrappers distinguishable.
+fn wrapped_metadata_fields(
+    prefix: &str,
+    original_mint: &Pubkey,
+    original_metadata: Option<&MetadataAccount>,
+) -> (String, String, String) {
+    match original_metadata {
+        Some(metadata) => (
+            format!("{}{}", prefix, metadata.name.trim_end_matches('\0')),
+            format!("{}{}", prefix, metadata.symbol.trim_end_matches('\0')),
+            metadata.uri.trim_end_matches('\0').to_string(),
+        ),
+        None => {
+            let mint = original_mint.to_string();
+            (
+                format!("{}{}..{}", prefix, &mint[..4], &mint[mint.len() - 4..]),
+                format!("{}{}", prefix, &mint[..4]),
+                String::new(),
+            )
+        }
+    }
+}
+
+/// CPI Token-2022 confidential-transfer `UpdateMint` with the settings currently on `config`.
+fn update_confidential_mint<'info>(
+    config: &Account<'info, WrapperConfig>,
+    wrapped_mint: &AccountInfo<'info>,
+    token_2022_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let seeds = &[
+        b"config",
+        config.original_mint.as_ref(),
+        &[config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let update_ix = confidential_transfer::instruction::update_mint(
+        token_2022_program.key,
+        wrapped_mint.key,
+        &config.key(),
+        &[],
+        config.auto_approve_new_accounts,
+        config.auditor_elgamal_pubkey.map(|k| k.into()),
+    )?;
+
+    invoke_signed(
+        &update_ix,
+        &[wrapped_mint.clone(), config.to_account_info()],
+        signer,
+    )?;
+    Ok(())
+}
+
+/// CPI Token-2022 `Pause`/`Resume` on the wrapped mint, signed by the Config PDA.
+fn set_wrapped_mint_paused<'info>(
+    config: &Account<'info, WrapperConfig>,
+    wrapped_mint: &AccountInfo<'info>,
+    token_2022_program: &AccountInfo<'info>,
+    paused: bool,
+) -> Result<()> {
+    let seeds = &[
+        b"config",
+        config.original_mint.as_ref(),
+        &[config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let ix = if paused {
+        pausable::instruction::pause(token_2022_program.key, wrapped_mint.key, &config.key(), &[])?
+    } else {
+        pausable::instruction::resume(token_2022_program.key, wrapped_mint.key, &config.key(), &[])?
+    };
+
+    invoke_signed(
+        &ix,
+        &[wrapped_mint.clone(), config.to_account_info()],
+        signer,
+    )?;
+    Ok(())
+}
+
+/// Whether the wrapped mint's `Pausable` extension currently halts mints, burns and transfers
+fn wrapped_mint_paused(wrapped_mint: &AccountInfo) -> Result<bool> {
+    let data = wrapped_mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(mint
+        .get_extension::<pausable::PausableConfig>()
+        .is_ok_and(|pausable| bool::from(pausable.paused)))
+}
+
+/// Vault balance not accounted for by circulating wrapped tokens, referral balances
+/// or accrued fees (e.g. tokens sent to the vault directly).
+fn vault_surplus(stats: &WrapperStats, vault_balance: u64) -> Result<u64> {
+    let backed_tokens = stats.total_wrapped
+        .checked_sub(stats.total_unwrapped)
+        .ok_or(WrapperError::Overflow)?;
+    let surplus = vault_balance
+        .checked_sub(backed_tokens)
+        .and_then(|v| v.checked_sub(stats.referral_fees_owed))
+        .and_then(|v| v.checked_sub(stats.fees_accrued))
+        .ok_or(WrapperError::InsufficientVaultBalance)?;
+    Ok(surplus)
+}
+
+/// Caller-supplied guard against fee changes landing before their wrap/unwrap:
+/// fails if the transaction runs after `deadline` (unix seconds) or the fee tops `max_fee`.
+fn check_user_limits(fee: u64, max_fee: u64, deadline: Option<i64>) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, WrapperError::DeadlineExpired);
+    }
+    require!(fee <= max_fee, WrapperError::FeeExceedsLimit);
+    Ok(())
+}
+
+/// Wrapped-fee mode: mint `amount` wrapped tokens to the fee treasury, signed by the Config PDA.
+fn mint_fee_to_treasury<'info>(
+    config: &Account<'info, WrapperConfig>,
+    wrapped_mint: &AccountInfo<'info>,
+    fee_treasury: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_2022_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let fee_treasury = fee_treasury.ok_or(WrapperError::InvalidFeeTreasury)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let seeds = &[
+        b"config",
+        config.original_mint.as_ref(),
+        &[config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let mint_to_accounts = anchor_spl::token_interface::MintTo {
+        mint: wrapped_mint.clone(),
+        to: fee_treasury.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_2022_program.clone(),
+        mint_to_accounts,
+        signer
+    );
+    anchor_spl::token_interface::mint_to(cpi_ctx, amount)
+}
+
+/// Wrapped-fee mode: move `amount` of the user's wrapped tokens to the fee treasury.
+fn transfer_fee_to_treasury<'info>(
+    user: &AccountInfo<'info>,
+    user_wrapped_account: &AccountInfo<'info>,
+    wrapped_mint: &InterfaceAccount<'info, Mint>,
+    fee_treasury: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_2022_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let fee_treasury = fee_treasury.ok_or(WrapperError::InvalidFeeTreasury)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let transfer_accounts = TransferChecked {
+        from: user_wrapped_account.clone(),
+        mint: wrapped_mint.to_account_info(),
+        to: fee_treasury.to_account_info(),
+        authority: user.clone(),
+    };
+    let cpi_ctx = CpiContext::new(token_2022_program.clone(), transfer_accounts);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, wrapped_mint.decimals)
+}
+
+/// Credit the referrer's share of `fee`; it stays in the vault until claimed.
+/// Returns the referrer (if any) and the amount credited.
+fn credit_referrer(
+    referrer_balance: Option<&mut Account<ReferrerBalance>>,
+    fee: u64,
+    referral_share_bps: u16,
+) -> Result<(Option<Pubkey>, u64)> {
+    match referrer_balance {
+        Some(referrer_balance) => {
+            let share = fees::referral_share(fee, referral_share_bps)?;
+            referrer_balance.accrued = referrer_balance.accrued.checked_add(share).ok_or(WrapperError::Overflow)?;
+            Ok((Some(referrer_balance.referrer), share))
+        }
+        None => Ok((None, 0)),
+    }
+}
+
+/// Move `amount` original tokens from the user into the vault and return what the vault
+/// actually received, plus the Token-2022 transfer fee withheld on the way.
+fn transfer_into_vault<'info>(
+    user: &Signer<'info>,
+    original_mint: &InterfaceAccount<'info, Mint>,
+    user_original_account: &InterfaceAccount<'info, TokenAccount>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    let vault_balance_before = vault.amount;
+    let transfer_accounts = TransferChecked {
+        from: user_original_account.to_account_info(),
+        mint: original_mint.to_account_info(),
+        to: vault.to_account_info(),
+        authority: user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.clone(), transfer_accounts);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, original_mint.decimals)?;
+
+    // Measure what actually arrived (Token-2022 transfer fees are withheld in the vault)
+    vault.reload()?;
+    let received = vault.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(WrapperError::Overflow)?;
+    require!(received > 0, WrapperError::ZeroAmount);
+    let transfer_fee = amount.checked_sub(received).ok_or(WrapperError::Overflow)?;
+    Ok((received, transfer_fee))
+}
+
+/// Accounts every wrap variant shares, borrowed from its context for `mint_wrapped`
+struct WrapAccounts<'a, 'info> {
+    user: &'a Signer<'info>,
+    original_mint: &'a InterfaceAccount<'info, Mint>,
+    wrapper_config: &'a Account<'info, WrapperConfig>,
+    wrapper_stats: &'a mut Account<'info, WrapperStats>,
+    user_volume: &'a mut Account<'info, UserVolume>,
+    user_volume_bump: u8,
+    referrer_balance: Option<&'a mut Account<'info, ReferrerBalance>>,
+    fee_treasury: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    wrapped_mint: &'a InterfaceAccount<'info, Mint>,
+    user_wrapped_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_2022_program: AccountInfo<'info>,
+}
+
+/// Second half of every wrap, once `received` original tokens are in the vault: take the
+/// fee (bounded by the caller's limits), credit the referrer, mint the net amount to the
+/// user and, in wrapped-fee mode, the protocol fee to the treasury. Updates stats and
+/// volume and emits `WrapEvent`. Returns the amount minted to the user.
+fn mint_wrapped(
+    accounts: WrapAccounts,
+    received: u64,
+    transfer_fee: u64,
+    max_fee: u64,
+    deadline: Option<i64>,
+) -> Result<u64> {
+    let config = accounts.wrapper_config;
+    let stats = accounts.wrapper_stats;
+
+    // 1. Calculate Fees on the received amount
+    let fee = fees::user_fee(received, config.wrap_fee_bps, config, accounts.user_volume)?;
+    check_user_limits(fee, max_fee, deadline)?;
+
+    let net_amount = received.checked_sub(fee).ok_or(WrapperError::Overflow)?;
+    let (referrer, referral_fee) = credit_referrer(accounts.referrer_balance, fee, config.referral_share_bps)?;
+
+    // 2. Mint Wrapped Token to User
+    // Sign with Config PDA seeds (authority)
+    let seeds = &[
+        b"config",
+        config.original_mint.as_ref(),
+        &[config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let mint_to_accounts = anchor_spl::token_interface::MintTo {
+        mint: accounts.wrapped_mint.to_account_info(),
+        to: accounts.user_wrapped_account.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let cpi_mint_ctx = CpiContext::new_with_signer(
+        accounts.token_2022_program.clone(),
+        mint_to_accounts,
+        signer
+    );
+    anchor_spl::token_interface::mint_to(cpi_mint_ctx, net_amount)?;
+
+    // 2.1 In wrapped-fee mode the protocol's part of the fee is minted to the treasury
+    let protocol_fee = fee.checked_sub(referral_fee).ok_or(WrapperError::Overflow)?;
+    let treasury_fee = if config.fee_currency == FeeCurrency::Wrapped {
+        mint_fee_to_treasury(
+            config,
+            &accounts.wrapped_mint.to_account_info(),
+            accounts.fee_treasury,
+            &accounts.token_2022_program,
+            protocol_fee,
+        )?;
+        protocol_fee
+    } else {
+        0
+    };
+
+    // 3. Update Stats
+    let minted = net_amount.checked_add(treasury_fee).ok_or(WrapperError::Overflow)?;
+    stats.total_wrapped = stats.total_wrapped.checked_add(minted).ok_or(WrapperError::Overflow)?;
+    stats.total_deposited = stats.total_deposited.checked_add(received).ok_or(WrapperError::Overflow)?;
+    stats.total_fees_collected = stats.total_fees_collected.checked_add(fee).ok_or(WrapperError::Overflow)?;
+    stats.referral_fees_owed = stats.referral_fees_owed.checked_add(referral_fee).ok_or(WrapperError::Overflow)?;
+    stats.accrue_fee(config.fee_currency, protocol_fee)?;
+
+    stats.total_transfer_fees = stats.total_transfer_fees.checked_add(transfer_fee).ok_or(WrapperError::Overflow)?;
+
+    // Track volume for fee tiers
+    accounts.user_volume.record(config.key(), accounts.user.key(), accounts.user_volume_bump, received)?;
+
+    emit!(WrapEvent {
+        user: accounts.user.key(),
+        original_mint: accounts.original_mint.key(),
+        wrapped_mint: accounts.wrapped_mint.key(),
+        amount: net_amount,
+        fee,
+        transfer_fee,
+        referrer,
+        referral_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(net_amount)
+}
+
+/// Accounts every unwrap variant shares, borrowed from its context for `burn_and_release`
+struct UnwrapAccounts<'a, 'info> {
+    user: &'a Signer<'info>,
+    original_mint: &'a InterfaceAccount<'info, Mint>,
+    wrapper_config: &'a Account<'info, WrapperConfig>,
+    wrapper_stats: &'a mut Account<'info, WrapperStats>,
+    user_volume: &'a mut Account<'info, UserVolume>,
+    user_volume_bump: u8,
+    referrer_balance: Option<&'a mut Account<'info, ReferrerBalance>>,
+    fee_treasury: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    wrapped_mint: &'a InterfaceAccount<'info, Mint>,
+    vault: &'a InterfaceAccount<'info, TokenAccount>,
+    user_wrapped_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    token_2022_program: AccountInfo<'info>,
+}
+
+/// Every unwrap once `amount` wrapped tokens sit in the user's public balance: take the
+/// fee (bounded by the caller's limits), credit the referrer, burn (moving the protocol
+/// fee to the treasury in wrapped-fee mode) and send the net original amount from the
+/// vault to `recipient`. Updates stats and volume and emits `UnwrapEvent`. Returns the
+/// amount sent from the vault.
+fn burn_and_release<'info>(
+    accounts: UnwrapAccounts<'_, 'info>,
+    recipient: AccountInfo<'info>,
+    amount: u64,
+    max_fee: u64,
+    deadline: Option<i64>,
+) -> Result<u64> {
+    let config = accounts.wrapper_config;
+    let stats = accounts.wrapper_stats;
+
+    // 1. Calculate Fees
+    let fee = fees::user_fee(amount, config.unwrap_fee_bps, config, accounts.user_volume)?;
+    check_user_limits(fee, max_fee, deadline)?;
+
+    let net_amount = amount.checked_sub(fee).ok_or(WrapperError::Overflow)?;
+    let (referrer, referral_fee) = credit_referrer(accounts.referrer_balance, fee, config.referral_share_bps)?;
+
+    // 1.5 Sanity check: ensure vault has enough tokens
+    require!(
+        accounts.vault.amount >= net_amount,
+        WrapperError::InsufficientVaultBalance
+    );
+
+    // In wrapped-fee mode the protocol's part of the fee goes to the treasury instead of being burned
+    let protocol_fee = fee.checked_sub(referral_fee).ok_or(WrapperError::Overflow)?;
+    let treasury_fee = if config.fee_currency == FeeCurrency::Wrapped {
+        transfer_fee_to_treasury(
+            &accounts.user.to_account_info(),
+            &accounts.user_wrapped_account.to_account_info(),
+            accounts.wrapped_mint,
+            accounts.fee_treasury,
+            &accounts.token_2022_program,
+            protocol_fee,
+        )?;
+        protocol_fee
+    } else {
+        0
+    };
+    let burn_amount = amount.checked_sub(treasury_fee).ok_or(WrapperError::Overflow)?;
+
+    // 2. Burn Wrapped Token
+    let burn_accounts = anchor_spl::token_interface::Burn {
+        mint: accounts.wrapped_mint.to_account_info(),
+        from: accounts.user_wrapped_account.to_account_info(),
+        authority: accounts.user.to_account_info(),
+    };
+    let cpi_burn_ctx = CpiContext::new(
+        accounts.token_2022_program.clone(),
+        burn_accounts
+    );
+    anchor_spl::token_interface::burn(cpi_burn_ctx, burn_amount)?;
+
+    // 3. Transfer Original Token from Vault
+    // The original mint may withhold a Token-2022 transfer fee from the recipient
+    let transfer_fee = original_mint_transfer_fee(
+        &accounts.original_mint.to_account_info(),
+        net_amount,
+    )?;
+
+    let seeds = &[
+        b"config",
+        config.original_mint.as_ref(),
+        &[config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_out_accounts = TransferChecked {
+        from: accounts.vault.to_account_info(),
+        mint: accounts.original_mint.to_account_info(),
+        to: recipient,
+        authority: config.to_account_info(), // Config is authority
+    };
+    let cpi_out_ctx = CpiContext::new_with_signer(
+        accounts.token_program.clone(),
+        transfer_out_accounts,
+        signer
+    );
+    anchor_spl::token_interface::transfer_checked(
+        cpi_out_ctx,
+        net_amount,
+        accounts.original_mint.decimals,
+    )?;
+
+    // 4. Update Stats
+    // NOTE: Track gross amount burned (not net) to match wrap accounting
+    stats.total_unwrapped = stats.total_unwrapped.checked_add(burn_amount).ok_or(WrapperError::Overflow)?;
+    stats.total_fees_collected = stats.total_fees_collected.checked_add(fee).ok_or(WrapperError::Overflow)?;
+    stats.referral_fees_owed = stats.referral_fees_owed.checked_add(referral_fee).ok_or(WrapperError::Overflow)?;
+    stats.accrue_fee(config.fee_currency, protocol_fee)?;
+
+    stats.total_transfer_fees = stats.total_transfer_fees.checked_add(transfer_fee).ok_or(WrapperError::Overflow)?;
+
+    // Track volume for fee tiers
+    accounts.user_volume.record(config.key(), accounts.user.key(), accounts.user_volume_bump, amount)?;
+
+    emit!(UnwrapEvent {
+        user: accounts.user.key(),
+        original_mint: accounts.original_mint.key(),
+        wrapped_mint: accounts.wrapped_mint.key(),
+        amount: net_amount,
+        fee,
+        transfer_fee,
+        referrer,
+        referral_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(net_amount)
+}
+
+/// Parameter checks shared by `queue_action` and `execute_action`.
+fn validate_admin_action(action: &AdminAction) -> Result<()> {
+    match action {
+        AdminAction::SetFees { wrap_fee_bps, unwrap_fee_bps } => {
+            require!(*wrap_fee_bps <= fees::MAX_FEE_BPS, WrapperError::FeeTooHigh);
+            require!(*unwrap_fee_bps <= fees::MAX_FEE_BPS, WrapperError::FeeTooHigh);
+        }
+        AdminAction::SetFeeSchedule { fee_schedule } => fee_schedule.validate()?,
+        AdminAction::SetFeeTiers { fee_tiers } => fees::validate_fee_tiers(fee_tiers)?,
+        AdminAction::ProposeAuthority { new_authority } => {
+            require!(*new_authority != Pubkey::default(), WrapperError::InvalidAuthority);
+        }
+        AdminAction::SetTimelockDelay { timelock_delay } => {
+            require!(
+                (0..=WrapperConfig::MAX_TIMELOCK_DELAY).contains(timelock_delay),
+                WrapperError::InvalidTimelockDelay
+            );
+        }
+        AdminAction::SetAuditor { .. }
+        | AdminAction::Unpause { .. }
+        | AdminAction::RevokeFeeExemption { .. }
+        | AdminAction::ClearFeeSplit => {}
+    }
+    Ok(())
+}
+
 #[program]
 pub mod c_spl_wrapper {
     use super::*;
 
+    /// One-time setup of the program-wide config, restricted to the upgrade authority
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        factory_admin: Pubkey,
+    ) -> Result<()> {
+        require!(factory_admin != Pubkey::default(), WrapperError::InvalidAuthority);
+
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.factory_admin = factory_admin;
+        program_config.wrapper_count = 0;
+        program_config.bump = ctx.bumps.program_config;
+
+        emit!(FactoryAdminUpdatedEvent {
+            old_factory_admin: Pubkey::default(),
+            new_factory_admin: factory_admin,
+        });
+        Ok(())
+    }
+
+    /// Read-only: the WrapperConfig addresses stored on one registry page, via return data
+    pub fn list_wrappers(ctx: Context<ListWrappers>, _page: u64) -> Result<Vec<Pubkey>> {
+        Ok(ctx.accounts.registry_page.wrappers.clone())
+    }
+
+    /// Hand the factory admin role to another key
+    pub fn set_factory_admin(ctx: Context<FactoryAdminOnly>, new_factory_admin: Pubkey) -> Result<()> {
+        require!(new_factory_admin != Pubkey::default(), WrapperError::InvalidAuthority);
+
+        let program_config = &mut ctx.accounts.program_config;
+        let old_factory_admin = program_config.factory_admin;
+        program_config.factory_admin = new_factory_admin;
+
+        emit!(FactoryAdminUpdatedEvent {
+            old_factory_admin,
+            new_factory_admin,
+        });
+        Ok(())
+    }
+
+    /// Create a wrapper. Only the factory admin or the original mint's mint authority
+    /// may call this, so nobody can squat the config PDA for someone else's mint.
     pub fn initialize(
         ctx: Context<Initialize>, 
         wrap_fee_bps: u16, 
         unwrap_fee_bps: u16,
         auditor_elgamal_pubkey: Option<[u8; 32]>,
+        auto_approve_new_accounts: bool,
+        metadata_prefix: String,
+        timelock_delay: i64,
     ) -> Result<()> {
         let config = &mut ctx.accounts.wrapper_config;
         let stats = &mut ctx.accounts.wrapper_stats;
 
-        // 1. Validate fees
-        require!(wrap_fee_bps <= 1000, WrapperError::FeeTooHigh);
-        require!(unwrap_fee_bps <= 1000, WrapperError::FeeTooHigh);
+        // 1. Validate caller and fees
+        let user_key = ctx.accounts.user.key();
+        let initialized_by = if ctx.accounts.original_mint.mint_authority == COption::Some(user_key) {
+            InitializerKind::MintAuthority
+        } else if user_key == ctx.accounts.program_config.factory_admin {
+            InitializerKind::FactoryAdmin
+        } else {
+            return err!(WrapperError::UnauthorizedInitializer);
+        };
+
+        require!(wrap_fee_bps <= fees::MAX_FEE_BPS, WrapperError::FeeTooHigh);
+        require!(unwrap_fee_bps <= fees::MAX_FEE_BPS, WrapperError::FeeTooHigh);
+        require!(
+            metadata_prefix.len() <= WrapperConfig::MAX_METADATA_PREFIX_LEN,
+            WrapperError::MetadataPrefixTooLong
+        );
+        require!(
+            (0..=WrapperConfig::MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            WrapperError::InvalidTimelockDelay
+        );
 
         // 2. Initialize Config PDA
         config.authority = ctx.accounts.user.key();
+        config.approver = ctx.accounts.user.key();
+        config.pauser = ctx.accounts.user.key();
+        config.fee_manager = ctx.accounts.user.key();
+        config.treasurer = ctx.accounts.user.key();
+        config.freezer = ctx.accounts.user.key();
+        config.auditor_manager = ctx.accounts.user.key();
+        config.guardian = Pubkey::default();
+        config.initialized_by = initialized_by;
+        config.pending_authority = None;
         config.original_mint = ctx.accounts.original_mint.key();
         config.wrapped_mint = ctx.accounts.wrapped_mint.key();
         config.vault = ctx.accounts.vault.key();
         config.wrap_fee_bps = wrap_fee_bps;
         config.unwrap_fee_bps = unwrap_fee_bps;
-        config.is_paused = false;
+        config.fee_schedule = FeeSchedule::DEFAULT;
+        config.fee_tiers = Vec::new();
+        config.referral_share_bps = 0;
+        config.fee_currency = FeeCurrency::Original;
+        config.fee_treasury = Pubkey::default();
+        config.wrap_paused = false;
+        config.unwrap_paused = false;
+        config.timelock_delay = timelock_delay;
+        config.next_action_id = 0;
         config.bump = ctx.bumps.wrapper_config;
         config.auditor_elgamal_pubkey = auditor_elgamal_pubkey;
+        config.auto_approve_new_accounts = auto_approve_new_accounts;
+        config.metadata_prefix = metadata_prefix.clone();
 
         // 3. Initialize Stats PDA
         stats.total_wrapped = 0;
         stats.total_unwrapped = 0;
         stats.total_deposited = 0;
         stats.total_fees_collected = 0;
+        stats.total_transfer_fees = 0;
+        stats.referral_fees_owed = 0;
+        stats.fees_accrued = 0;
+        stats.wrapped_fees_collected = 0;
         stats.bump = ctx.bumps.wrapper_stats;
 
         // 3. Manually create and initialize Wrapped Mint
         
         let space = ExtensionType::try_calculate_account_len::<MintState>(&[
             ExtensionType::ConfidentialTransferMint,
+            ExtensionType::MetadataPointer,
+            ExtensionType::Pausable,
         ])?;
 
-        let lamports = (Rent::get()?).minimum_balance(space);
+        // TokenMetadata is variable-length and reallocated by Token-2022 on init,
+        // so the account is created at the fixed size but funded for the full size.
+        let config_key = ctx.accounts.wrapper_config.key();
+        let (name, symbol, uri) = wrapped_metadata_fields(
+            &metadata_prefix,
+            &ctx.accounts.original_mint.key(),
+            ctx.accounts.original_metadata.as_deref(),
+        );
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(config_key))?,
+            mint: ctx.accounts.wrapped_mint.key(),
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![],
+        };
+
+        let lamports = (Rent::get()?).minimum_balance(space + token_metadata.tlv_size_of()?);
         let original_mint_key = ctx.accounts.original_mint.key();
         let mint_seeds = &[
             b"mint",
@@ -97,13 +741,11 @@ pub mod c_spl_wrapper {
         )?;
 
         // B. Initialize Confidential Transfer Extension
-        let config_key = ctx.accounts.wrapper_config.key();
-        
         let init_ct_ix = confidential_transfer::instruction::initialize_mint(
             ctx.accounts.token_2022_program.key,
             ctx.accounts.wrapped_mint.key,
             Some(config_key), // CT authority = WrapperConfig PDA
-            true, // auto_approve_new_accounts
+            auto_approve_new_accounts, // false = approver must approve each account
             auditor_elgamal_pubkey.map(|k| k.into()), // Option<[u8; 32]> into Option<PodElGamalPubkey> 
         )?;
         
@@ -115,6 +757,31 @@ pub mod c_spl_wrapper {
             ],
         )?;
 
+        // B2. Initialize Metadata Pointer (metadata lives on the mint itself)
+        let init_pointer_ix = metadata_pointer::instruction::initialize(
+            ctx.accounts.token_2022_program.key,
+            ctx.accounts.wrapped_mint.key,
+            Some(config_key),
+            Some(ctx.accounts.wrapped_mint.key()),
+        )?;
+
+        invoke(
+            &init_pointer_ix,
+            &[ctx.accounts.wrapped_mint.to_account_info()],
+        )?;
+
+        // B3. Initialize Pausable (pause authority = WrapperConfig PDA)
+        let init_pausable_ix = pausable::instruction::initialize(
+            ctx.accounts.token_2022_program.key,
+            ctx.accounts.wrapped_mint.key,
+            &config_key,
+        )?;
+
+        invoke(
+            &init_pausable_ix,
+            &[ctx.accounts.wrapped_mint.to_account_info()],
+        )?;
+
         // C. Initialize Mint
         // spl-token-2022 v0.9/v3/v8: initialize_mint(prog, mint, authority, freeze_authority, decimals)
         // Expects references for Pubkeys usually? 
@@ -137,236 +804,797 @@ pub mod c_spl_wrapper {
             ],
         )?;
 
+        // D. Initialize Token Metadata, signed by the Config PDA as mint authority
+        let config_seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[ctx.bumps.wrapper_config],
+        ];
+        let config_signer = &[&config_seeds[..]];
+
+        let init_metadata_ix = spl_token_metadata_interface::instruction::initialize(
+            ctx.accounts.token_2022_program.key,
+            ctx.accounts.wrapped_mint.key,
+            &config_key,
+            ctx.accounts.wrapped_mint.key,
+            &config_key,
+            name,
+            symbol,
+            uri,
+        );
+
+        invoke_signed(
+            &init_metadata_ix,
+            &[
+                ctx.accounts.wrapped_mint.to_account_info(),
+                ctx.accounts.wrapper_config.to_account_info(),
+            ],
+            config_signer,
+        )?;
+
+        // E. Append to the global registry
+        let program_config = &mut ctx.accounts.program_config;
+        let registry_index = program_config.wrapper_count;
+        let registry_page = &mut ctx.accounts.registry_page;
+        if registry_page.wrappers.is_empty() {
+            registry_page.page = program_config.current_registry_page();
+            registry_page.bump = ctx.bumps.registry_page;
+        }
+        registry_page.wrappers.push(config_key);
+        program_config.wrapper_count = registry_index.checked_add(1).ok_or(WrapperError::Overflow)?;
+
+        emit!(WrapperInitializedEvent {
+            wrapper_config: config_key,
+            original_mint: original_mint_key,
+            wrapped_mint: ctx.accounts.wrapped_mint.key(),
+            authority: user_key,
+            initialized_by,
+            registry_index,
+        });
+
         msg!("Initialized Wrapper for Mint: {}", ctx.accounts.original_mint.key());
         Ok(())
     }
 
-    pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
-        let config = &ctx.accounts.wrapper_config;
-        let stats = &mut ctx.accounts.wrapper_stats;
-
+    /// Wrap `amount` original tokens and return the wrapped amount minted to the user.
+    /// Fails if the fee would exceed `max_fee` or the transaction lands after
+    /// `deadline` (unix seconds, if given).
+    pub fn wrap(ctx: Context<Wrap>, amount: u64, max_fee: u64, deadline: Option<i64>) -> Result<u64> {
         // 1. Validation
         require!(amount > 0, WrapperError::ZeroAmount);
-        require!(!config.is_paused, WrapperError::WrapperPaused);
+        require!(!ctx.accounts.wrapper_config.wrap_paused, WrapperError::WrapPaused);
+
+        // 2. Transfer Original Token to Vault
+        let (received, transfer_fee) = transfer_into_vault(
+            &ctx.accounts.user,
+            &ctx.accounts.original_mint,
+            &ctx.accounts.user_original_account,
+            &mut ctx.accounts.vault,
+            &ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
 
-        // 2. Calculate Fees
-        let fee = amount
-            .checked_mul(config.wrap_fee_bps as u64)
-            .ok_or(WrapperError::FeeCalculationError)?
-            .checked_div(10_000)
-            .ok_or(WrapperError::FeeCalculationError)?;
+        // 3. Fee, mint, stats and event
+        let accounts = ctx.accounts;
+        mint_wrapped(
+            WrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            received,
+            transfer_fee,
+            max_fee,
+            deadline,
+        )
+    }
 
-        let net_amount = amount.checked_sub(fee).ok_or(WrapperError::Overflow)?;
+    /// Wrap so that exactly `net_amount` wrapped tokens are minted to the user.
+    /// The input is derived from the current fee settings (and the original mint's
+    /// transfer fee) and must not exceed `max_amount_in`.
+    pub fn wrap_exact_out(
+        ctx: Context<Wrap>,
+        net_amount: u64,
+        max_amount_in: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+        require!(net_amount > 0, WrapperError::ZeroAmount);
+
+        // 1. Amount the vault must receive for `net_amount` to be left after our fee
+        let received = fees::user_gross_for_net(
+            net_amount,
+            config.wrap_fee_bps,
+            config,
+            &ctx.accounts.user_volume,
+        )?;
 
-        // 3. Transfer Original Token to Vault
-        let transfer_accounts = Transfer {
-            from: ctx.accounts.user_original_account.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            transfer_accounts
-        );
-        anchor_spl::token::transfer(cpi_ctx, amount)?;
+        // 2. Gross up for the original mint's transfer fee on the way into the vault
+        let amount = original_mint_amount_for_received(
+            &ctx.accounts.original_mint.to_account_info(),
+            received,
+        )?;
+        require!(amount <= max_amount_in, WrapperError::MaxInputExceeded);
 
-        // 4. Mint Wrapped Token to User
-        // Sign with Config PDA seeds (authority)
-        let original_mint_key = ctx.accounts.original_mint.key();
-        let seeds = &[
-            b"config",
-            original_mint_key.as_ref(),
-            &[config.bump],
-        ];
-        let signer = &[&seeds[..]];
+        // 3. The regular wrap recomputes the fee on what arrives; `max_amount_in` already bounds it
+        let minted = wrap(ctx, amount, u64::MAX, deadline)?;
+        require!(minted == net_amount, WrapperError::ExactOutputMismatch);
+        Ok(())
+    }
 
-        let mint_to_accounts = anchor_spl::token_interface::MintTo {
-            mint: ctx.accounts.wrapped_mint.to_account_info(),
-            to: ctx.accounts.user_wrapped_account.to_account_info(),
-            authority: ctx.accounts.wrapper_config.to_account_info(),
-        };
-        let cpi_mint_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_2022_program.to_account_info(),
-            mint_to_accounts,
-            signer
-        );
-        anchor_spl::token_interface::mint_to(cpi_mint_ctx, net_amount)?;
-        
-        // 5. Update Stats
-        stats.total_wrapped = stats.total_wrapped.checked_add(net_amount).ok_or(WrapperError::Overflow)?;
-        stats.total_deposited = stats.total_deposited.checked_add(amount).ok_or(WrapperError::Overflow)?;
-        stats.total_fees_collected = stats.total_fees_collected.checked_add(fee).ok_or(WrapperError::Overflow)?;
-
-        emit!(WrapEvent {
-            user: ctx.accounts.user.key(),
-            original_mint: ctx.accounts.original_mint.key(),
-            wrapped_mint: ctx.accounts.wrapped_mint.key(),
-            amount: net_amount,
-            fee,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /// Wrap and move the minted amount straight into the confidential pending balance.
+    /// The user's wrapped account must already be set up via `configure_confidential_account`.
+    pub fn wrap_and_deposit(
+        ctx: Context<WrapAndDeposit>,
+        amount: u64,
+        max_fee: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // 1. Validation
+        require!(amount > 0, WrapperError::ZeroAmount);
+        require!(!ctx.accounts.wrapper_config.wrap_paused, WrapperError::WrapPaused);
+
+        // 2. Transfer Original Token to Vault
+        let (received, transfer_fee) = transfer_into_vault(
+            &ctx.accounts.user,
+            &ctx.accounts.original_mint,
+            &ctx.accounts.user_original_account,
+            &mut ctx.accounts.vault,
+            &ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+
+        // 3. Fee, mint, stats and event, as in `wrap`
+        let accounts = ctx.accounts;
+        let net_amount = mint_wrapped(
+            WrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            received,
+            transfer_fee,
+            max_fee,
+            deadline,
+        )?;
+
+        // 4. Deposit the freshly minted amount into the confidential pending balance
+        let deposit_ix = confidential_transfer::instruction::deposit(
+            accounts.token_2022_program.key,
+            &accounts.user_wrapped_account.key(),
+            &accounts.wrapped_mint.key(),
+            net_amount,
+            accounts.wrapped_mint.decimals,
+            accounts.user.key,
+            &[],
+        )?;
+
+        invoke(
+            &deposit_ix,
+            &[
+                accounts.user_wrapped_account.to_account_info(),
+                accounts.wrapped_mint.to_account_info(),
+                accounts.user.to_account_info(),
+            ],
+        )?;
 
         Ok(())
     }
 
-    pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
+    /// Unwrap `amount` wrapped tokens and return the original amount sent from the vault.
+    /// Fails if the fee would exceed `max_fee` or the transaction lands after
+    /// `deadline` (unix seconds, if given).
+    pub fn unwrap(ctx: Context<Unwrap>, amount: u64, max_fee: u64, deadline: Option<i64>) -> Result<u64> {
+        // 1. Validation
+        require!(amount > 0, WrapperError::ZeroAmount);
+        require!(!ctx.accounts.wrapper_config.unwrap_paused, WrapperError::UnwrapPaused);
+
+        // 2. Fee, burn, release, stats and event
+        let accounts = ctx.accounts;
+        let recipient = accounts.user_original_account.to_account_info();
+        burn_and_release(
+            UnwrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                vault: &accounts.vault,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_program: accounts.token_program.to_account_info(),
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            recipient,
+            amount,
+            max_fee,
+            deadline,
+        )
+    }
+
+    /// Unwrap so that exactly `net_amount` original tokens leave the vault for the user
+    /// (before any transfer fee the original mint withholds). The wrapped tokens
+    /// spent are derived from the current fee settings and must not exceed `max_amount_in`.
+    pub fn unwrap_exact_out(
+        ctx: Context<Unwrap>,
+        net_amount: u64,
+        max_amount_in: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
         let config = &ctx.accounts.wrapper_config;
-        let stats = &mut ctx.accounts.wrapper_stats;
+        require!(net_amount > 0, WrapperError::ZeroAmount);
+
+        let amount = fees::user_gross_for_net(
+            net_amount,
+            config.unwrap_fee_bps,
+            config,
+            &ctx.accounts.user_volume,
+        )?;
+        require!(amount <= max_amount_in, WrapperError::MaxInputExceeded);
+
+        let released = unwrap(ctx, amount, u64::MAX, deadline)?;
+        require!(released == net_amount, WrapperError::ExactOutputMismatch);
+        Ok(())
+    }
 
+    /// Unwrap directly from the confidential available balance.
+    /// Withdraws `amount` to the public balance using pre-verified proof context
+    /// accounts, then burns and releases the original tokens in the same instruction.
+    /// `max_fee` and `deadline` guard the fee as in `unwrap`.
+    pub fn unwrap_confidential(
+        ctx: Context<UnwrapConfidential>,
+        amount: u64,
+        new_decryptable_available_balance: [u8; 36],
+        max_fee: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
         // 1. Validation
         require!(amount > 0, WrapperError::ZeroAmount);
-        require!(!config.is_paused, WrapperError::WrapperPaused);
+        require!(!ctx.accounts.wrapper_config.unwrap_paused, WrapperError::UnwrapPaused);
+
+        // 2. Confidential Withdraw into the public balance
+        let withdraw_ix = confidential_transfer::instruction::inner_withdraw(
+            ctx.accounts.token_2022_program.key,
+            &ctx.accounts.user_wrapped_account.key(),
+            &ctx.accounts.wrapped_mint.key(),
+            amount,
+            ctx.accounts.wrapped_mint.decimals,
+            &new_decryptable_available_balance.into(),
+            ctx.accounts.user.key,
+            &[],
+            ProofLocation::ContextStateAccount(ctx.accounts.equality_proof_context.key),
+            ProofLocation::ContextStateAccount(ctx.accounts.range_proof_context.key),
+        )?;
+
+        invoke(
+            &withdraw_ix,
+            &[
+                ctx.accounts.user_wrapped_account.to_account_info(),
+                ctx.accounts.wrapped_mint.to_account_info(),
+                ctx.accounts.equality_proof_context.to_account_info(),
+                ctx.accounts.range_proof_context.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+            ],
+        )?;
+
+        // 3. Fee, burn, release, stats and event, as in `unwrap`
+        let accounts = ctx.accounts;
+        let recipient = accounts.user_original_account.to_account_info();
+        burn_and_release(
+            UnwrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                vault: &accounts.vault,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_program: accounts.token_program.to_account_info(),
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            recipient,
+            amount,
+            max_fee,
+            deadline,
+        )?;
 
-        // 2. Calculate Fees
-        let fee = amount
-            .checked_mul(config.unwrap_fee_bps as u64)
-            .ok_or(WrapperError::FeeCalculationError)?
-            .checked_div(10_000)
-            .ok_or(WrapperError::FeeCalculationError)?;
+        Ok(())
+    }
 
-        let net_amount = amount.checked_sub(fee).ok_or(WrapperError::Overflow)?;
+    /// Wrap native SOL: lamports go straight into the wSOL vault
+    pub fn wrap_sol(ctx: Context<WrapSol>, amount: u64, max_fee: u64, deadline: Option<i64>) -> Result<()> {
+        // 1. Validation
+        require!(amount > 0, WrapperError::ZeroAmount);
+        require!(!ctx.accounts.wrapper_config.wrap_paused, WrapperError::WrapPaused);
 
-        // 2.5 Sanity check: ensure vault has enough tokens
-        require!(
-            ctx.accounts.vault.amount >= net_amount,
-            WrapperError::InsufficientVaultBalance
+        // 2. Move lamports into the wSOL vault and sync its token balance
+        let transfer_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts
         );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        // 3. Burn Wrapped Token
-        let burn_accounts = anchor_spl::token_interface::Burn {
-            mint: ctx.accounts.wrapped_mint.to_account_info(),
-            from: ctx.accounts.user_wrapped_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+        let sync_accounts = anchor_spl::token::SyncNative {
+            account: ctx.accounts.vault.to_account_info(),
         };
-        let cpi_burn_ctx = CpiContext::new(
-            ctx.accounts.token_2022_program.to_account_info(),
-            burn_accounts
+        let cpi_sync_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            sync_accounts
         );
-        anchor_spl::token_interface::burn(cpi_burn_ctx, amount)?;
+        anchor_spl::token::sync_native(cpi_sync_ctx)?;
+
+        // 3. Fee, mint, stats and event, as in `wrap` (native SOL carries no transfer fee)
+        let accounts = ctx.accounts;
+        mint_wrapped(
+            WrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            amount,
+            0,
+            max_fee,
+            deadline,
+        )?;
 
-        // 4. Transfer Original Token from Vault
-        let original_mint_key = ctx.accounts.original_mint.key();
+        Ok(())
+    }
+
+    /// Unwrap to native SOL via a temporary wSOL account that is closed to the user
+    pub fn unwrap_sol(ctx: Context<UnwrapSol>, amount: u64, max_fee: u64, deadline: Option<i64>) -> Result<()> {
+        // 1. Validation
+        require!(amount > 0, WrapperError::ZeroAmount);
+        require!(!ctx.accounts.wrapper_config.unwrap_paused, WrapperError::UnwrapPaused);
+
+        // 2. Fee, burn, stats and event as in `unwrap`, releasing wSOL into the temporary account
+        let accounts = ctx.accounts;
+        let recipient = accounts.temp_sol_account.to_account_info();
+        burn_and_release(
+            UnwrapAccounts {
+                user: &accounts.user,
+                original_mint: &accounts.original_mint,
+                wrapper_config: &accounts.wrapper_config,
+                wrapper_stats: &mut accounts.wrapper_stats,
+                user_volume: &mut accounts.user_volume,
+                user_volume_bump: ctx.bumps.user_volume,
+                referrer_balance: accounts.referrer_balance.as_mut(),
+                fee_treasury: accounts.fee_treasury.as_ref(),
+                wrapped_mint: &accounts.wrapped_mint,
+                vault: &accounts.vault,
+                user_wrapped_account: &accounts.user_wrapped_account,
+                token_program: accounts.token_program.to_account_info(),
+                token_2022_program: accounts.token_2022_program.to_account_info(),
+            },
+            recipient,
+            amount,
+            max_fee,
+            deadline,
+        )?;
+
+        // 3. Close the temporary account, releasing lamports (and its rent) to the user
+        let config = &accounts.wrapper_config;
         let seeds = &[
             b"config",
-            original_mint_key.as_ref(),
+            config.original_mint.as_ref(),
             &[config.bump],
         ];
         let signer = &[&seeds[..]];
 
-        let transfer_out_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.user_original_account.to_account_info(),
-            authority: ctx.accounts.wrapper_config.to_account_info(), // Config is authority
+        let close_accounts = anchor_spl::token::CloseAccount {
+            account: accounts.temp_sol_account.to_account_info(),
+            destination: accounts.user.to_account_info(),
+            authority: config.to_account_info(),
         };
-        let cpi_out_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            transfer_out_accounts,
+        let cpi_close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            close_accounts,
             signer
         );
-        anchor_spl::token::transfer(cpi_out_ctx, net_amount)?;
+        anchor_spl::token::close_account(cpi_close_ctx)?;
 
-        // 5. Update Stats
-        // NOTE: Track gross amount burned (not net) to match wrap accounting
-        stats.total_unwrapped = stats.total_unwrapped.checked_add(amount).ok_or(WrapperError::Overflow)?;
-        stats.total_fees_collected = stats.total_fees_collected.checked_add(fee).ok_or(WrapperError::Overflow)?;
+        Ok(())
+    }
 
-        emit!(UnwrapEvent {
-            user: ctx.accounts.user.key(),
-            original_mint: ctx.accounts.original_mint.key(),
-            wrapped_mint: ctx.accounts.wrapped_mint.key(),
-            amount: net_amount,
-            fee,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /// Pause both wrap and unwrap, and optionally all transfers of the wrapped token
+    pub fn pause(ctx: Context<PauseCtx>, pause_transfers: bool) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        config.wrap_paused = true;
+        config.unwrap_paused = true;
+
+        if pause_transfers {
+            set_wrapped_mint_paused(
+                &ctx.accounts.wrapper_config,
+                &ctx.accounts.wrapped_mint.to_account_info(),
+                &ctx.accounts.token_2022_program.to_account_info(),
+                true,
+            )?;
+        }
 
+        emit!(PauseEvent {
+            authority: ctx.accounts.pauser.key(),
+            direction: PauseDirection::Both,
+            is_paused: true,
+            transfers_affected: pause_transfers,
+        });
         Ok(())
     }
 
-    pub fn pause(ctx: Context<AdminOnly>) -> Result<()> {
+    /// Emergency pause by the guardian key. Same effect as `pause`; there is no
+    /// guardian counterpart for unpausing.
+    pub fn guardian_pause(ctx: Context<GuardianPause>, pause_transfers: bool) -> Result<()> {
         let config = &mut ctx.accounts.wrapper_config;
-        config.is_paused = true;
+        config.wrap_paused = true;
+        config.unwrap_paused = true;
+
+        if pause_transfers {
+            set_wrapped_mint_paused(
+                &ctx.accounts.wrapper_config,
+                &ctx.accounts.wrapped_mint.to_account_info(),
+                &ctx.accounts.token_2022_program.to_account_info(),
+                true,
+            )?;
+        }
+
         emit!(PauseEvent {
-            authority: ctx.accounts.authority.key(),
+            authority: ctx.accounts.guardian.key(),
+            direction: PauseDirection::Both,
             is_paused: true,
+            transfers_affected: pause_transfers,
         });
         Ok(())
     }
 
-    pub fn unpause(ctx: Context<AdminOnly>) -> Result<()> {
+    /// Set or clear (`Pubkey::default()`) the guardian key
+    pub fn set_guardian(ctx: Context<AdminOnly>, new_guardian: Pubkey) -> Result<()> {
         let config = &mut ctx.accounts.wrapper_config;
-        config.is_paused = false;
-        emit!(PauseEvent {
+        let old_guardian = config.guardian;
+        config.guardian = new_guardian;
+
+        emit!(GuardianUpdatedEvent {
             authority: ctx.accounts.authority.key(),
-            is_paused: false,
+            old_guardian,
+            new_guardian,
         });
         Ok(())
     }
 
-    pub fn set_fees(
-        ctx: Context<AdminOnly>, 
-        wrap_fee_bps: u16, 
-        unwrap_fee_bps: u16
-    ) -> Result<()> {
-        require!(wrap_fee_bps <= 1000, WrapperError::FeeTooHigh);
-        require!(unwrap_fee_bps <= 1000, WrapperError::FeeTooHigh);
+    /// Exempt a user from wrap/unwrap fees. This only ever lowers a fee, so it applies
+    /// immediately; revoking an exemption goes through the timelock.
+    pub fn grant_fee_exemption(ctx: Context<GrantFeeExemption>, user: Pubkey) -> Result<()> {
+        let config_key = ctx.accounts.wrapper_config.key();
+        let user_volume = &mut ctx.accounts.user_volume;
+        user_volume.record(config_key, user, ctx.bumps.user_volume, 0)?;
+        user_volume.fee_exempt = true;
+
+        emit!(FeeExemptionUpdatedEvent {
+            authority: ctx.accounts.fee_manager.key(),
+            user,
+            exempt: true,
+        });
+        Ok(())
+    }
 
+    /// Pause new wraps only (e.g. during an incident with the original token)
+    pub fn pause_wrap(ctx: Context<PauserOnly>) -> Result<()> {
         let config = &mut ctx.accounts.wrapper_config;
-        config.wrap_fee_bps = wrap_fee_bps;
-        config.unwrap_fee_bps = unwrap_fee_bps;
-        
-        emit!(FeesUpdatedEvent {
-            authority: ctx.accounts.authority.key(),
-            wrap_fee_bps,
-            unwrap_fee_bps,
+        config.wrap_paused = true;
+        emit!(PauseEvent {
+            authority: ctx.accounts.pauser.key(),
+            direction: PauseDirection::Wrap,
+            is_paused: true,
+            transfers_affected: false,
         });
         Ok(())
     }
 
-    pub fn set_authority(ctx: Context<AdminOnly>, new_authority: Pubkey) -> Result<()> {
-        require!(new_authority != Pubkey::default(), WrapperError::InvalidAuthority);
-        
+    /// Pause unwraps only (e.g. during a vault migration)
+    pub fn pause_unwrap(ctx: Context<PauserOnly>) -> Result<()> {
         let config = &mut ctx.accounts.wrapper_config;
-        let old_authority = config.authority;
-        config.authority = new_authority;
-        
-        emit!(AuthorityUpdatedEvent {
-            old_authority,
-            new_authority,
+        config.unwrap_paused = true;
+        emit!(PauseEvent {
+            authority: ctx.accounts.pauser.key(),
+            direction: PauseDirection::Unwrap,
+            is_paused: true,
+            transfers_affected: false,
         });
         Ok(())
     }
 
-    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
-        let config = &ctx.accounts.wrapper_config;
-        let stats = &ctx.accounts.wrapper_stats;
-        
-        // Calculate backed tokens (circulating wrapped supply)
-        let backed_tokens = stats.total_wrapped
-            .checked_sub(stats.total_unwrapped)
+    /// Queue a fee, fee tier or exemption change, authority nomination, auditor rotation,
+    /// unpause or timelock change. It can be executed once `timelock_delay` has elapsed.
+    pub fn queue_action(ctx: Context<QueueAction>, action: AdminAction) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        let proposer = ctx.accounts.proposer.key();
+        require_keys_eq!(action.proposer(config), proposer, WrapperError::Unauthorized);
+        validate_admin_action(&action)?;
+
+        let id = config.next_action_id;
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(config.timelock_delay)
             .ok_or(WrapperError::Overflow)?;
-        
-        // Get current vault balance
-        let vault_balance = ctx.accounts.vault.amount;
-        
-        // Withdrawable = vault - backed (the surplus from fees)
-        let withdrawable = vault_balance
-            .checked_sub(backed_tokens)
-            .ok_or(WrapperError::InsufficientVaultBalance)?;
-        
-        require!(withdrawable > 0, WrapperError::ZeroAmount);
-        
-        // Transfer fees to authority
-        let original_mint_key = ctx.accounts.original_mint.key();
-        let seeds = &[
-            b"config",
-            original_mint_key.as_ref(),
-            &[config.bump],
-        ];
-        let signer = &[&seeds[..]];
+        config.next_action_id = id.checked_add(1).ok_or(WrapperError::Overflow)?;
+
+        let queued = &mut ctx.accounts.timelocked_action;
+        queued.wrapper_config = config.key();
+        queued.id = id;
+        queued.proposer = proposer;
+        queued.action = action.clone();
+        queued.eta = eta;
+        queued.bump = ctx.bumps.timelocked_action;
+
+        emit!(ActionQueuedEvent {
+            wrapper_config: config.key(),
+            id,
+            proposer,
+            action,
+            eta,
+        });
+        Ok(())
+    }
+
+    /// Apply a queued action after its ETA. Permissionless; rent goes back to the proposer.
+    pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+        let queued = &ctx.accounts.timelocked_action;
+        let (id, proposer, action) = (queued.id, queued.proposer, queued.action.clone());
+        require!(
+            Clock::get()?.unix_timestamp >= queued.eta,
+            WrapperError::TimelockNotExpired
+        );
+
+        let config = &mut ctx.accounts.wrapper_config;
+        // Actions queued by a key that has since lost the role are void
+        require_keys_eq!(action.proposer(config), proposer, WrapperError::Unauthorized);
+        validate_admin_action(&action)?;
+
+        match action.clone() {
+            AdminAction::SetFees { wrap_fee_bps, unwrap_fee_bps } => {
+                config.wrap_fee_bps = wrap_fee_bps;
+                config.unwrap_fee_bps = unwrap_fee_bps;
+
+                emit!(FeesUpdatedEvent {
+                    authority: proposer,
+                    wrap_fee_bps,
+                    unwrap_fee_bps,
+                });
+            }
+            AdminAction::SetFeeSchedule { fee_schedule } => {
+                config.fee_schedule = fee_schedule;
+
+                emit!(FeeScheduleUpdatedEvent {
+                    authority: proposer,
+                    fee_schedule,
+                });
+            }
+            AdminAction::ProposeAuthority { new_authority } => {
+                config.pending_authority = Some(new_authority);
+
+                emit!(AuthorityProposedEvent {
+                    authority: proposer,
+                    pending_authority: new_authority,
+                });
+            }
+            AdminAction::SetAuditor { auditor_elgamal_pubkey } => {
+                let old_auditor = config.auditor_elgamal_pubkey;
+                config.auditor_elgamal_pubkey = auditor_elgamal_pubkey;
+
+                update_confidential_mint(
+                    &ctx.accounts.wrapper_config,
+                    &ctx.accounts.wrapped_mint.to_account_info(),
+                    &ctx.accounts.token_2022_program.to_account_info(),
+                )?;
+
+                emit!(AuditorUpdatedEvent {
+                    authority: proposer,
+                    old_auditor,
+                    new_auditor: auditor_elgamal_pubkey,
+                });
+            }
+            AdminAction::Unpause { direction, resume_transfers } => {
+                match direction {
+                    PauseDirection::Wrap => config.wrap_paused = false,
+                    PauseDirection::Unwrap => config.unwrap_paused = false,
+                    PauseDirection::Both => {
+                        config.wrap_paused = false;
+                        config.unwrap_paused = false;
+                    }
+                }
+
+                // Wraps and unwraps also need the mint itself running, so once both
+                // directions are open a paused mint resumes even without `resume_transfers`
+                let resume_transfers = resume_transfers
+                    || (!config.wrap_paused
+                        && !config.unwrap_paused
+                        && wrapped_mint_paused(&ctx.accounts.wrapped_mint.to_account_info())?);
+                if resume_transfers {
+                    set_wrapped_mint_paused(
+                        &ctx.accounts.wrapper_config,
+                        &ctx.accounts.wrapped_mint.to_account_info(),
+                        &ctx.accounts.token_2022_program.to_account_info(),
+                        false,
+                    )?;
+                }
+
+                emit!(PauseEvent {
+                    authority: proposer,
+                    direction,
+                    is_paused: false,
+                    transfers_affected: resume_transfers,
+                });
+            }
+            AdminAction::SetTimelockDelay { timelock_delay } => {
+                let old_timelock_delay = config.timelock_delay;
+                config.timelock_delay = timelock_delay;
+
+                emit!(TimelockDelayUpdatedEvent {
+                    authority: proposer,
+                    old_timelock_delay,
+                    new_timelock_delay: timelock_delay,
+                });
+            }
+            AdminAction::SetFeeTiers { fee_tiers } => {
+                config.fee_tiers = fee_tiers.clone();
+
+                emit!(FeeTiersUpdatedEvent {
+                    authority: proposer,
+                    fee_tiers,
+                });
+            }
+            AdminAction::RevokeFeeExemption { user } => {
+                let config_key = config.key();
+                let user_volume = ctx
+                    .accounts
+                    .user_volume
+                    .as_mut()
+                    .ok_or(WrapperError::InvalidUserVolume)?;
+                require!(
+                    user_volume.wrapper_config == config_key && user_volume.user == user,
+                    WrapperError::InvalidUserVolume
+                );
+                user_volume.fee_exempt = false;
+
+                emit!(FeeExemptionUpdatedEvent {
+                    authority: proposer,
+                    user,
+                    exempt: false,
+                });
+            }
+            AdminAction::ClearFeeSplit => {
+                // Closing the split re-enables `withdraw_fees`; the rent goes back to the treasurer
+                let fee_split = ctx
+                    .accounts
+                    .fee_split
+                    .as_ref()
+                    .ok_or(WrapperError::FeeSplitNotConfigured)?;
+                fee_split.close(ctx.accounts.proposer.to_account_info())?;
+
+                emit!(FeeSplitUpdatedEvent {
+                    authority: proposer,
+                    recipients: Vec::new(),
+                });
+            }
+        }
+
+        emit!(ActionExecutedEvent {
+            wrapper_config: ctx.accounts.wrapper_config.key(),
+            id,
+            executor: ctx.accounts.executor.key(),
+            action,
+        });
+        Ok(())
+    }
+
+    /// Drop a queued action before it is executed
+    pub fn cancel_action(ctx: Context<CancelAction>) -> Result<()> {
+        let queued = &ctx.accounts.timelocked_action;
+
+        emit!(ActionCancelledEvent {
+            wrapper_config: ctx.accounts.wrapper_config.key(),
+            id: queued.id,
+            cancelled_by: ctx.accounts.canceller.key(),
+            action: queued.action.clone(),
+        });
+        Ok(())
+    }
+
+    /// Step 1 of an authority handover: queue the nomination of `new_authority`.
+    /// Shorthand for `queue_action` with `ProposeAuthority`, so it waits out the timelock.
+    pub fn propose_authority(ctx: Context<QueueAction>, new_authority: Pubkey) -> Result<()> {
+        queue_action(ctx, AdminAction::ProposeAuthority { new_authority })
+    }
+
+    /// Step 2 of an authority handover: the nominee signs to take over
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        let old_authority = config.authority;
+        let new_authority = ctx.accounts.new_authority.key();
+        config.authority = new_authority;
+        config.pending_authority = None;
+
+        emit!(AuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a pending authority nomination
+    pub fn cancel_authority_transfer(ctx: Context<AdminOnly>) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        let pending_authority = config
+            .pending_authority
+            .take()
+            .ok_or(WrapperError::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelledEvent {
+            authority: ctx.accounts.authority.key(),
+            pending_authority,
+        });
+        Ok(())
+    }
+
+    /// Withdraw up to the accrued, not yet withdrawn fees to the treasurer.
+    /// Only original-token fees accrue here; wrapped-mode fees are already in `fee_treasury`.
+    /// Disabled while a fee split is configured: fees then leave only via `distribute_fees`
+    /// until the treasurer clears the split with the timelocked `ClearFeeSplit` action.
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+        let stats = &mut ctx.accounts.wrapper_stats;
+
+        require!(ctx.accounts.fee_split.data_is_empty(), WrapperError::FeeSplitConfigured);
+        require!(amount > 0, WrapperError::ZeroAmount);
+        stats.fees_accrued = stats.fees_accrued
+            .checked_sub(amount)
+            .ok_or(WrapperError::InsufficientAccruedFees)?;
+
+        // Transfer fees to treasurer
+        let original_mint_key = ctx.accounts.original_mint.key();
+        let seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
 
-        let transfer_accounts = Transfer {
+        let transfer_accounts = TransferChecked {
             from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.authority_token_account.to_account_info(),
+            mint: ctx.accounts.original_mint.to_account_info(),
+            to: ctx.accounts.treasurer_token_account.to_account_info(),
             authority: ctx.accounts.wrapper_config.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(
@@ -374,13 +1602,461 @@ pub mod c_spl_wrapper {
             transfer_accounts,
             signer
         );
-        anchor_spl::token::transfer(cpi_ctx, withdrawable)?;
+        anchor_spl::token_interface::transfer_checked(
+            cpi_ctx,
+            amount,
+            ctx.accounts.original_mint.decimals,
+        )?;
 
         emit!(FeesWithdrawnEvent {
+            authority: ctx.accounts.treasurer.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move vault tokens that are neither backing nor fees (donations, mistaken
+    /// transfers) to an account chosen by the admin, e.g. to refund the sender
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+
+        let surplus = vault_surplus(&ctx.accounts.wrapper_stats, ctx.accounts.vault.amount)?;
+        require!(amount > 0, WrapperError::ZeroAmount);
+        require!(amount <= surplus, WrapperError::InsufficientVaultBalance);
+
+        let original_mint_key = ctx.accounts.original_mint.key();
+        let seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let transfer_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.original_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.wrapper_config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer
+        );
+        anchor_spl::token_interface::transfer_checked(
+            cpi_ctx,
+            amount,
+            ctx.accounts.original_mint.decimals,
+        )?;
+
+        emit!(SurplusSweptEvent {
             authority: ctx.accounts.authority.key(),
-            amount: withdrawable,
+            destination: ctx.accounts.destination.key(),
+            amount,
+            surplus,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Choose whether fees are kept as original tokens in the vault (withdrawn via
+    /// `withdraw_fees`/`distribute_fees`) or paid as wrapped tokens to `fee_treasury`.
+    /// Original-token fees accrued before a switch stay withdrawable.
+    pub fn set_fee_currency(ctx: Context<SetFeeCurrency>, fee_currency: FeeCurrency) -> Result<()> {
+        let fee_treasury = match fee_currency {
+            FeeCurrency::Original => Pubkey::default(),
+            FeeCurrency::Wrapped => ctx
+                .accounts
+                .fee_treasury
+                .as_ref()
+                .ok_or(WrapperError::InvalidFeeTreasury)?
+                .key(),
+        };
+
+        let config = &mut ctx.accounts.wrapper_config;
+        config.fee_currency = fee_currency;
+        config.fee_treasury = fee_treasury;
+
+        emit!(FeeCurrencyUpdatedEvent {
+            authority: ctx.accounts.treasurer.key(),
+            fee_currency,
+            fee_treasury,
+        });
+        Ok(())
+    }
+
+    /// Set the share of each fee credited to the referrer on `wrap`/`unwrap`
+    pub fn set_referral_share(ctx: Context<FeeManagerOnly>, referral_share_bps: u16) -> Result<()> {
+        require!(
+            referral_share_bps as u64 <= fees::BPS_DENOMINATOR,
+            WrapperError::InvalidReferralShare
+        );
+
+        let config = &mut ctx.accounts.wrapper_config;
+        config.referral_share_bps = referral_share_bps;
+
+        emit!(ReferralShareUpdatedEvent {
+            authority: ctx.accounts.fee_manager.key(),
+            referral_share_bps,
+        });
+        Ok(())
+    }
+
+    /// Register a partner wallet as a referrer so its balance can be passed to `wrap`/`unwrap`
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        let referrer_balance = &mut ctx.accounts.referrer_balance;
+        referrer_balance.wrapper_config = ctx.accounts.wrapper_config.key();
+        referrer_balance.referrer = referrer;
+        referrer_balance.accrued = 0;
+        referrer_balance.bump = ctx.bumps.referrer_balance;
+
+        emit!(ReferrerRegisteredEvent {
+            authority: ctx.accounts.fee_manager.key(),
+            referrer,
+        });
+        Ok(())
+    }
+
+    /// Pay out a referrer's accrued share of fees from the vault
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+        let amount = ctx.accounts.referrer_balance.accrued;
+        require!(amount > 0, WrapperError::ZeroAmount);
+
+        ctx.accounts.referrer_balance.accrued = 0;
+        let stats = &mut ctx.accounts.wrapper_stats;
+        stats.referral_fees_owed = stats.referral_fees_owed.checked_sub(amount).ok_or(WrapperError::Overflow)?;
+
+        let original_mint_key = ctx.accounts.original_mint.key();
+        let seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let transfer_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.original_mint.to_account_info(),
+            to: ctx.accounts.referrer_token_account.to_account_info(),
+            authority: ctx.accounts.wrapper_config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer
+        );
+        anchor_spl::token_interface::transfer_checked(
+            cpi_ctx,
+            amount,
+            ctx.accounts.original_mint.decimals,
+        )?;
+
+        emit!(ReferralFeesClaimedEvent {
+            referrer: ctx.accounts.referrer.key(),
+            amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
+        Ok(())
+    }
+
+    /// Configure how `distribute_fees` splits collected fees. Removing the split again
+    /// goes through the timelocked `ClearFeeSplit` action.
+    /// `remaining_accounts` are the recipients' token accounts, in split order; each must
+    /// hold the original mint so `distribute_fees` can always pay every recipient.
+    pub fn set_fee_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetFeeSplit<'info>>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        fees::validate_fee_split(&recipients)?;
+        require!(
+            ctx.remaining_accounts.len() == recipients.len(),
+            WrapperError::FeeRecipientMismatch
+        );
+        for (recipient, account_info) in recipients.iter().zip(ctx.remaining_accounts.iter()) {
+            require_keys_eq!(
+                account_info.key(),
+                recipient.token_account,
+                WrapperError::FeeRecipientMismatch
+            );
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+            require_keys_eq!(
+                token_account.mint,
+                ctx.accounts.wrapper_config.original_mint,
+                WrapperError::InvalidFeeSplit
+            );
+        }
+
+        let fee_split = &mut ctx.accounts.fee_split;
+        fee_split.wrapper_config = ctx.accounts.wrapper_config.key();
+        fee_split.recipients = recipients.clone();
+        fee_split.bump = ctx.bumps.fee_split;
+
+        emit!(FeeSplitUpdatedEvent {
+            authority: ctx.accounts.treasurer.key(),
+            recipients,
+        });
+        Ok(())
+    }
+
+    /// Permissionless crank: pay collected fees out to the fee split recipients.
+    /// `remaining_accounts` are the recipients' token accounts, in split order.
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+        let recipients = &ctx.accounts.fee_split.recipients;
+        require!(
+            ctx.remaining_accounts.len() == recipients.len(),
+            WrapperError::FeeRecipientMismatch
+        );
+
+        let distributable = ctx.accounts.wrapper_stats.fees_accrued;
+        require!(distributable > 0, WrapperError::ZeroAmount);
+        let amounts = fees::split_amount(distributable, recipients)?;
+        ctx.accounts.wrapper_stats.fees_accrued = 0;
+
+        let original_mint_key = ctx.accounts.original_mint.key();
+        let seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        for ((recipient, amount), token_account) in recipients
+            .iter()
+            .zip(amounts)
+            .zip(ctx.remaining_accounts.iter())
+        {
+            require_keys_eq!(
+                token_account.key(),
+                recipient.token_account,
+                WrapperError::FeeRecipientMismatch
+            );
+            if amount == 0 {
+                continue;
+            }
+
+            let transfer_accounts = TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.original_mint.to_account_info(),
+                to: token_account.clone(),
+                authority: ctx.accounts.wrapper_config.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_accounts,
+                signer
+            );
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                amount,
+                ctx.accounts.original_mint.decimals,
+            )?;
+
+            emit!(FeesDistributedEvent {
+                recipient: recipient.token_account,
+                share_bps: recipient.share_bps,
+                amount,
+                timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Refresh the wrapped mint's TokenMetadata from the original's Metaplex metadata.
+    /// Without it, falls back to the shortened-address name as `initialize` does.
+    pub fn sync_metadata(
+        ctx: Context<SyncMetadata>,
+        metadata_prefix: Option<String>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        if let Some(prefix) = metadata_prefix {
+            require!(
+                prefix.len() <= WrapperConfig::MAX_METADATA_PREFIX_LEN,
+                WrapperError::MetadataPrefixTooLong
+            );
+            config.metadata_prefix = prefix;
+        }
+
+        let (name, symbol, uri) = wrapped_metadata_fields(
+            &config.metadata_prefix,
+            &config.original_mint,
+            ctx.accounts.original_metadata.as_deref(),
+        );
+
+        // 1. Work out which fields changed and how much the mint may grow
+        let mint_info = ctx.accounts.wrapped_mint.to_account_info();
+        let (current, data_len) = {
+            let data = mint_info.try_borrow_data()?;
+            let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+            (mint.get_variable_len_extension::<TokenMetadata>()?, data.len())
+        };
+
+        let updates: Vec<(Field, String, &String)> = [
+            (Field::Name, name, &current.name),
+            (Field::Symbol, symbol, &current.symbol),
+            (Field::Uri, uri, &current.uri),
+        ]
+        .into_iter()
+        .filter(|(_, value, old)| value != *old)
+        .collect();
+
+        // Each update reallocs the mint, so fund the largest intermediate size up front
+        let growth: usize = updates
+            .iter()
+            .map(|(_, value, old)| value.len().saturating_sub(old.len()))
+            .sum();
+        let required_lamports = (Rent::get()?).minimum_balance(data_len + growth);
+        let top_up = required_lamports.saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.authority.key,
+                    mint_info.key,
+                    top_up,
+                ),
+                &[
+                    ctx.accounts.authority.to_account_info(),
+                    mint_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        // 2. Apply field updates, signed by the Config PDA as update authority
+        let original_mint_key = config.original_mint;
+        let seeds = &[
+            b"config",
+            original_mint_key.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        for (field, value, _) in updates {
+            let update_ix = spl_token_metadata_interface::instruction::update_field(
+                ctx.accounts.token_2022_program.key,
+                mint_info.key,
+                &ctx.accounts.wrapper_config.key(),
+                field,
+                value,
+            );
+            invoke_signed(
+                &update_ix,
+                &[
+                    mint_info.clone(),
+                    ctx.accounts.wrapper_config.to_account_info(),
+                ],
+                signer,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Toggle whether new confidential accounts are approved automatically
+    pub fn set_auto_approve(
+        ctx: Context<UpdateConfidentialMint>,
+        auto_approve_new_accounts: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        config.auto_approve_new_accounts = auto_approve_new_accounts;
+
+        update_confidential_mint(
+            &ctx.accounts.wrapper_config,
+            &ctx.accounts.wrapped_mint.to_account_info(),
+            &ctx.accounts.token_2022_program.to_account_info(),
+        )?;
+
+        emit!(AutoApproveUpdatedEvent {
+            authority: ctx.accounts.auditor_manager.key(),
+            auto_approve_new_accounts,
+        });
+        Ok(())
+    }
+
+    /// Assign a role to a key, replacing any previous holder
+    pub fn grant_role(ctx: Context<AdminOnly>, role: Role, account: Pubkey) -> Result<()> {
+        require!(account != Pubkey::default(), WrapperError::InvalidAuthority);
+
+        let config = &mut ctx.accounts.wrapper_config;
+        let previous = config.role_holder(role);
+        config.set_role_holder(role, account);
+
+        emit!(RoleGrantedEvent {
+            authority: ctx.accounts.authority.key(),
+            role,
+            account,
+            previous,
+        });
+        Ok(())
+    }
+
+    /// Clear a role; instructions gated by it are disabled until it is granted again
+    pub fn revoke_role(ctx: Context<AdminOnly>, role: Role) -> Result<()> {
+        let config = &mut ctx.accounts.wrapper_config;
+        let account = config.role_holder(role);
+        require!(account != Pubkey::default(), WrapperError::RoleNotAssigned);
+        config.set_role_holder(role, Pubkey::default());
+
+        emit!(RoleRevokedEvent {
+            authority: ctx.accounts.authority.key(),
+            role,
+            account,
+        });
+        Ok(())
+    }
+
+    /// Approve a wrapped token account for confidential transfers (manual approval mode)
+    pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+        let config = &ctx.accounts.wrapper_config;
+        let seeds = &[
+            b"config",
+            config.original_mint.as_ref(),
+            &[config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let approve_ix = confidential_transfer::instruction::approve_account(
+            ctx.accounts.token_2022_program.key,
+            &ctx.accounts.target_account.key(),
+            &ctx.accounts.wrapped_mint.key(),
+            &config.key(),
+            &[],
+        )?;
+
+        invoke_signed(
+            &approve_ix,
+            &[
+                ctx.accounts.target_account.to_account_info(),
+                ctx.accounts.wrapped_mint.to_account_info(),
+                ctx.accounts.wrapper_config.to_account_info(),
+            ],
+            signer,
+        )?;
+
+        // Record the approval for audit
+        let timestamp = Clock::get()?.unix_timestamp;
+        let approval = &mut ctx.accounts.approval;
+        approval.wrapped_mint = ctx.accounts.wrapped_mint.key();
+        approval.token_account = ctx.accounts.target_account.key();
+        approval.owner = ctx.accounts.target_account.owner;
+        approval.approved_by = ctx.accounts.approver.key();
+        approval.approved_at = timestamp;
+        approval.bump = ctx.bumps.approval;
+
+        emit!(ConfidentialAccountApprovedEvent {
+            approver: ctx.accounts.approver.key(),
+            account: ctx.accounts.target_account.key(),
+            owner: ctx.accounts.target_account.owner,
+            timestamp,
+        });
 
         Ok(())
     }
@@ -447,158 +2123,1106 @@ pub mod c_spl_wrapper {
         Ok(())
     }
 
-    /// Configure an account for confidential transfers
+    /// Configure an account for confidential transfers.
+    /// The pubkey validity proof is read either from `proof_context` (a pre-verified
+    /// context state account) or from the instruction at `proof_instruction_offset`
+    /// relative to this one, and must prove `elgamal_pubkey`.
     pub fn configure_confidential_account(
         ctx: Context<ConfigureConfidentialAccount>,
-        _elgamal_pubkey: [u8; 32],
+        elgamal_pubkey: [u8; 32],
+        decryptable_zero_balance: [u8; 36],
+        maximum_pending_balance_credit_counter: u64,
+        proof_instruction_offset: i8,
+    ) -> Result<()> {
+        let expected_pubkey: PodElGamalPubkey = elgamal_pubkey.into();
+        let proof_ix;
+
+        // 1. Locate the proof and check it proves the client's ElGamal pubkey
+        let proof_location = match &ctx.accounts.proof_context {
+            Some(proof_context) => {
+                require_keys_eq!(
+                    *proof_context.owner,
+                    zk_elgamal_proof_program::id(),
+                    WrapperError::InvalidProof
+                );
+                let data = proof_context.try_borrow_data()?;
+                let state = ProofContextState::<PubkeyValidityProofContext>::try_from_bytes(&data)
+                    .map_err(|_| error!(WrapperError::InvalidProof))?;
+                require!(
+                    state.proof_type == ProofType::PubkeyValidity.into(),
+                    WrapperError::InvalidProof
+                );
+                require!(
+                    state.proof_context.pubkey == expected_pubkey,
+                    WrapperError::ElGamalPubkeyMismatch
+                );
+                ProofLocation::ContextStateAccount(proof_context.key)
+            }
+            None => {
+                let offset = NonZeroI8::new(proof_instruction_offset)
+                    .ok_or(WrapperError::InvalidProof)?;
+                proof_ix = get_instruction_relative(
+                    offset.get() as i64,
+                    &ctx.accounts.instructions_sysvar.to_account_info(),
+                )?;
+                require_keys_eq!(
+                    proof_ix.program_id,
+                    zk_elgamal_proof_program::id(),
+                    WrapperError::InvalidProof
+                );
+                require!(
+                    ProofInstruction::instruction_type(&proof_ix.data)
+                        == Some(ProofInstruction::VerifyPubkeyValidity),
+                    WrapperError::InvalidProof
+                );
+                let proof_data = ProofInstruction::proof_data::<
+                    PubkeyValidityProofData,
+                    PubkeyValidityProofContext,
+                >(&proof_ix.data)
+                .ok_or(WrapperError::InvalidProof)?;
+                require!(
+                    proof_data.context.pubkey == expected_pubkey,
+                    WrapperError::ElGamalPubkeyMismatch
+                );
+                ProofLocation::InstructionOffset(offset, ProofData::InstructionData(proof_data))
+            }
+        };
+
+        // 2. CPI Token-2022 ConfigureAccount
+        let configure_ix = confidential_transfer::instruction::inner_configure_account(
+            ctx.accounts.token_2022_program.key,
+            &ctx.accounts.user_wrapped_account.key(),
+            &ctx.accounts.wrapped_mint.key(),
+            &decryptable_zero_balance.into(),
+            maximum_pending_balance_credit_counter,
+            ctx.accounts.user.key,
+            &[],
+            proof_location,
+        )?;
+
+        let mut account_infos = vec![
+            ctx.accounts.user_wrapped_account.to_account_info(),
+            ctx.accounts.wrapped_mint.to_account_info(),
+            ctx.accounts.instructions_sysvar.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+        ];
+        if let Some(proof_context) = &ctx.accounts.proof_context {
+            account_infos.push(proof_context.to_account_info());
+        }
+
+        invoke(&configure_ix, &account_infos)?;
+
+        Ok(())
+    }
+
+    /// Apply pending confidential balance to available balance
+    pub fn apply_pending_balance(
+        ctx: Context<ApplyPendingBalance>,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: [u8; 36],
     ) -> Result<()> {
         let token_program = &ctx.accounts.token_2022_program;
-        
+
         // Manual instruction construction
-        // NOTE: This currently requires an external ZK proof if not a fresh account
+        // TokenInstruction::ConfidentialTransferExtension = 27
+        // ConfidentialTransferInstruction::ApplyPendingBalance = 8
         
-        let mut data = Vec::with_capacity(47);
+        // Data layout:
+        // [0..1] TokenInstruction tag (27)
+        // [1..2] ConfidentialTransferInstruction tag (8)
+        // [2..10] expected_pending_balance_credit_counter (u64 le)
+        // [10..46] new_decryptable_available_balance (36 bytes)
+
+        let mut data = Vec::with_capacity(46);
         data.push(27); // ConfidentialTransferExtension
-        data.push(2);  // ConfigureAccount
-        data.extend_from_slice(&[0u8; 36]); // decryptable_zero_balance
-        data.extend_from_slice(&65535u64.to_le_bytes()); // maximum_pending_balance_credit_counter
-        data.push(-1i8 as u8); // proof_instruction_offset
+        data.push(8);  // ApplyPendingBalance
+        data.extend_from_slice(&expected_pending_balance_credit_counter.to_le_bytes());
+        data.extend_from_slice(&new_decryptable_available_balance);
+
+        let accounts = vec![
+            AccountMeta::new(ctx.accounts.user_wrapped_account.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.user.key(), true),
+        ];
+
+        let ix = Instruction {
+            program_id: *token_program.key,
+            accounts,
+            data,
+        };
+
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.user_wrapped_account.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"program_config"],
+        bump,
+        space = ProgramConfig::LEN
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::CSplWrapper>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(payer.key()) @ WrapperError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(page: u64)]
+pub struct ListWrappers<'info> {
+    #[account(
+        seeds = [b"registry", &page.to_le_bytes()],
+        bump = registry_page.bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+}
+
+#[derive(Accounts)]
+pub struct FactoryAdminOnly<'info> {
+    #[account(
+        mut,
+        has_one = factory_admin @ WrapperError::Unauthorized,
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    pub factory_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"registry", &program_config.current_registry_page().to_le_bytes()],
+        bump,
+        space = RegistryPage::LEN
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+    
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump,
+        space = WrapperConfig::LEN
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump,
+        space = WrapperStats::LEN
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    /// CHECK: Manually initialized in handler
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"vault", original_mint.key().as_ref()],
+        bump,
+        token::mint = original_mint,
+        token::authority = wrapper_config,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Original mint's Metaplex metadata, if it has one
+    #[account(
+        seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), original_mint.key().as_ref()],
+        seeds::program = METADATA_PROGRAM_ID,
+        bump,
+    )]
+    pub original_metadata: Option<Account<'info, MetadataAccount>>,
+
+    pub system_program: Program<'info, System>,
+    /// Token program that owns the original mint (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Wrap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut, 
+        token::mint = original_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_original_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = wrapped_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct WrapAndDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = original_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_original_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Must already be configured for confidential transfers
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = user,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unwrap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut, 
+        token::mint = original_mint,
+        token::token_program = token_program,
+    )]
+    pub user_original_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = user,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapConfidential<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = original_mint,
+        token::token_program = token_program,
+    )]
+    pub user_original_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = user,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Ciphertext-commitment equality proof context state, verified by Token-2022
+    pub equality_proof_context: UncheckedAccount<'info>,
+
+    /// CHECK: Batched range proof (u64) context state, verified by Token-2022
+    pub range_proof_context: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = NATIVE_MINT @ WrapperError::NotNativeMint)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    /// wSOL vault
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = wrapped_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = NATIVE_MINT @ WrapperError::NotNativeMint)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+        has_one = original_mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Receives fees in wrapped tokens when `fee_currency` is `Wrapped`
+    #[account(
+        mut,
+        address = wrapper_config.fee_treasury @ WrapperError::InvalidFeeTreasury,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional referrer credited with `referral_share_bps` of the fee
+    #[account(
+        mut,
+        has_one = wrapper_config,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer_balance.referrer.as_ref()],
+        bump = referrer_balance.bump,
+        constraint = referrer_balance.referrer != user.key() @ WrapperError::SelfReferral,
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", original_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    /// wSOL vault
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Temporary wSOL account, created and closed within this instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"sol_unwrap", original_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = original_mint,
+        token::authority = wrapper_config,
+        token::token_program = token_program,
+    )]
+    pub temp_sol_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = user,
+    )]
+    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminOnly<'info> {
+    #[account(
+        mut, 
+        has_one = authority @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = wrapper_config.pending_authority == Some(new_authority.key()) @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauserOnly<'info> {
+    #[account(
+        mut,
+        has_one = pauser @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub pauser: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    #[account(
+        mut,
+        has_one = guardian @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub guardian: Signer<'info>,
+
+    #[account(mut)]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"action", wrapper_config.key().as_ref(), &wrapper_config.next_action_id.to_le_bytes()],
+        bump,
+        space = TimelockedAction::LEN
+    )]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+
+    /// Must hold the role the action requires (checked in handler)
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = wrapper_config,
+        has_one = proposer,
+        seeds = [b"action", wrapper_config.key().as_ref(), &timelocked_action.id.to_le_bytes()],
+        bump = timelocked_action.bump,
+    )]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+
+    /// CHECK: Rent refund destination, matched against `timelocked_action.proposer`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Anyone may execute once the ETA has passed
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only needed for `RevokeFeeExemption`; checked against the action's user
+    #[account(mut)]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+
+    /// Only needed for `ClearFeeSplit`
+    #[account(
+        mut,
+        seeds = [b"fee_split", wrapper_config.key().as_ref()],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Option<Account<'info, FeeSplit>>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    #[account(
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = wrapper_config,
+        has_one = proposer,
+        seeds = [b"action", wrapper_config.key().as_ref(), &timelocked_action.id.to_le_bytes()],
+        bump = timelocked_action.bump,
+    )]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+
+    /// CHECK: Rent refund destination, matched against `timelocked_action.proposer`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Admin authority, or the proposer withdrawing their own action
+    #[account(
+        constraint = canceller.key() == wrapper_config.authority
+            || canceller.key() == timelocked_action.proposer @ WrapperError::Unauthorized,
+    )]
+    pub canceller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FeeManagerOnly<'info> {
+    #[account(
+        mut,
+        has_one = fee_manager @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub fee_manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GrantFeeExemption<'info> {
+    #[account(
+        has_one = fee_manager @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_manager,
+        seeds = [b"user_volume", wrapper_config.key().as_ref(), user.as_ref()],
+        bump,
+        space = UserVolume::LEN
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    #[account(mut)]
+    pub fee_manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PauseCtx<'info> {
+    #[account(
+        mut,
+        has_one = pauser @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
+    pub pauser: Signer<'info>,
+
+    #[account(mut)]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        has_one = treasurer @ WrapperError::Unauthorized,
+        has_one = original_mint @ WrapperError::InvalidMintPair,
+        has_one = vault,
+        seeds = [b"config", original_mint.key().as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
 
-        let accounts = vec![
-            AccountMeta::new(ctx.accounts.user_wrapped_account.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.wrapped_mint.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.instructions_sysvar.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.user.key(), true),
-        ];
+    #[account(
+        mut,
+        seeds = [b"stats", original_mint.key().as_ref()],
+        bump = wrapper_stats.bump,
+    )]
+    pub wrapper_stats: Account<'info, WrapperStats>,
 
-        let ix = Instruction {
-            program_id: *token_program.key,
-            accounts,
-            data,
-        };
+    /// CHECK: Fee split PDA; must not exist for direct withdrawals
+    #[account(
+        seeds = [b"fee_split", wrapper_config.key().as_ref()],
+        bump,
+    )]
+    pub fee_split: UncheckedAccount<'info>,
 
-        invoke(
-            &ix,
-            &[
-                ctx.accounts.user_wrapped_account.to_account_info(),
-                ctx.accounts.wrapped_mint.to_account_info(),
-                ctx.accounts.instructions_sysvar.to_account_info(),
-                ctx.accounts.user.to_account_info(),
-            ],
-        )?;
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
-        Ok(())
-    }
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
 
-    /// Apply pending confidential balance to available balance
-    pub fn apply_pending_balance(
-        ctx: Context<ApplyPendingBalance>,
-        expected_pending_balance_credit_counter: u64,
-        new_decryptable_available_balance: [u8; 36],
-    ) -> Result<()> {
-        let token_program = &ctx.accounts.token_2022_program;
+    /// Treasurer's token account to receive fees
+    #[account(
+        mut,
+        token::mint = original_mint,
+        token::authority = treasurer,
+        token::token_program = token_program,
+    )]
+    pub treasurer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-        // Manual instruction construction
-        // TokenInstruction::ConfidentialTransferExtension = 27
-        // ConfidentialTransferInstruction::ApplyPendingBalance = 8
-        
-        // Data layout:
-        // [0..1] TokenInstruction tag (27)
-        // [1..2] ConfidentialTransferInstruction tag (8)
-        // [2..10] expected_pending_balance_credit_counter (u64 le)
-        // [10..46] new_decryptable_available_balance (36 bytes)
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
-        let mut data = Vec::with_capacity(46);
-        data.push(27); // ConfidentialTransferExtension
-        data.push(8);  // ApplyPendingBalance
-        data.extend_from_slice(&expected_pending_balance_credit_counter.to_le_bytes());
-        data.extend_from_slice(&new_decryptable_available_balance);
+#[derive(Accounts)]
+pub struct SetFeeCurrency<'info> {
+    #[account(
+        mut,
+        has_one = treasurer @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
 
-        let accounts = vec![
-            AccountMeta::new(ctx.accounts.user_wrapped_account.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.user.key(), true),
-        ];
+    pub treasurer: Signer<'info>,
 
-        let ix = Instruction {
-            program_id: *token_program.key,
-            accounts,
-            data,
-        };
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
 
-        invoke(
-            &ix,
-            &[
-                ctx.accounts.user_wrapped_account.to_account_info(),
-                ctx.accounts.user.to_account_info(),
-            ],
-        )?;
+    /// Wrapped-token account for fees; required when switching to `Wrapped`
+    #[account(
+        token::mint = wrapped_mint,
+        token::token_program = token_2022_program,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
 
-        Ok(())
-    }
+    pub token_2022_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
-    
+pub struct SweepSurplus<'info> {
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
     #[account(
-        init,
-        payer = user,
+        has_one = authority @ WrapperError::Unauthorized,
+        has_one = original_mint @ WrapperError::InvalidMintPair,
+        has_one = vault,
         seeds = [b"config", original_mint.key().as_ref()],
-        bump,
-        space = WrapperConfig::LEN
+        bump = wrapper_config.bump,
     )]
     pub wrapper_config: Account<'info, WrapperConfig>,
 
     #[account(
-        init,
-        payer = user,
         seeds = [b"stats", original_mint.key().as_ref()],
-        bump,
-        space = WrapperStats::LEN
+        bump = wrapper_stats.bump,
     )]
     pub wrapper_stats: Account<'info, WrapperStats>,
 
-    /// CHECK: Manually initialized in handler
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
     #[account(
         mut,
-        seeds = [b"mint", original_mint.key().as_ref()],
-        bump,
+        token::mint = original_mint,
+        token::token_program = token_program,
     )]
-    pub wrapped_mint: UncheckedAccount<'info>,
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        has_one = fee_manager @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
 
     #[account(
         init,
-        payer = user,
-        seeds = [b"vault", original_mint.key().as_ref()],
+        payer = fee_manager,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer.as_ref()],
         bump,
-        token::mint = original_mint,
-        token::authority = wrapper_config,
+        space = ReferrerBalance::LEN
     )]
-    pub vault: Account<'info, anchor_spl::token::TokenAccount>,
+    pub referrer_balance: Account<'info, ReferrerBalance>,
+
+    #[account(mut)]
+    pub fee_manager: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub token_2022_program: Program<'info, Token2022>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct Wrap<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
-    
+pub struct ClaimReferralFees<'info> {
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
     #[account(
+        has_one = original_mint @ WrapperError::InvalidMintPair,
+        has_one = vault,
         seeds = [b"config", original_mint.key().as_ref()],
         bump = wrapper_config.bump,
-        has_one = original_mint,
-        has_one = wrapped_mint,
-        has_one = vault,
     )]
     pub wrapper_config: Account<'info, WrapperConfig>,
 
@@ -611,49 +3235,62 @@ pub struct Wrap<'info> {
 
     #[account(
         mut,
-        seeds = [b"mint", original_mint.key().as_ref()],
-        bump,
+        has_one = wrapper_config,
+        has_one = referrer @ WrapperError::Unauthorized,
+        seeds = [b"referrer", wrapper_config.key().as_ref(), referrer.key().as_ref()],
+        bump = referrer_balance.bump,
     )]
-    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+    pub referrer_balance: Account<'info, ReferrerBalance>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub referrer: Signer<'info>,
 
     #[account(
-        mut, 
+        mut,
         token::mint = original_mint,
-        token::authority = user,
+        token::token_program = token_program,
     )]
-    pub user_original_account: Account<'info, anchor_spl::token::TokenAccount>,
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub vault: Account<'info, anchor_spl::token::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(
+        has_one = treasurer @ WrapperError::Unauthorized,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
+    )]
+    pub wrapper_config: Account<'info, WrapperConfig>,
 
     #[account(
         init_if_needed,
-        payer = user,
-        associated_token::mint = wrapped_mint,
-        associated_token::authority = user,
-        associated_token::token_program = token_2022_program,
+        payer = treasurer,
+        seeds = [b"fee_split", wrapper_config.key().as_ref()],
+        bump,
+        space = FeeSplit::LEN
     )]
-    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
-    pub token_2022_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct Unwrap<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
-    
+pub struct DistributeFees<'info> {
+    #[account(mint::token_program = token_program)]
+    pub original_mint: InterfaceAccount<'info, Mint>,
+
     #[account(
+        has_one = original_mint @ WrapperError::InvalidMintPair,
+        has_one = vault,
         seeds = [b"config", original_mint.key().as_ref()],
         bump = wrapper_config.bump,
-        has_one = original_mint,
-        has_one = wrapped_mint,
-        has_one = vault,
     )]
     pub wrapper_config: Account<'info, WrapperConfig>,
 
@@ -665,88 +3302,109 @@ pub struct Unwrap<'info> {
     pub wrapper_stats: Account<'info, WrapperStats>,
 
     #[account(
-        mut,
-        seeds = [b"mint", original_mint.key().as_ref()],
-        bump,
+        has_one = wrapper_config,
+        seeds = [b"fee_split", wrapper_config.key().as_ref()],
+        bump = fee_split.bump,
     )]
-    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
+#[derive(Accounts)]
+pub struct SyncMetadata<'info> {
     #[account(
-        mut, 
-        token::mint = original_mint,
+        mut,
+        has_one = authority @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
+        bump = wrapper_config.bump,
     )]
-    pub user_original_account: Account<'info, anchor_spl::token::TokenAccount>,
+    pub wrapper_config: Account<'info, WrapperConfig>,
+
+    /// Pays for any metadata growth
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
     #[account(mut)]
-    pub vault: Account<'info, anchor_spl::token::TokenAccount>,
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
 
+    /// Original mint's Metaplex metadata, if it has one
     #[account(
-        mut,
-        token::mint = wrapped_mint,
-        token::authority = user,
+        seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), wrapper_config.original_mint.as_ref()],
+        seeds::program = METADATA_PROGRAM_ID,
+        bump,
     )]
-    pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
+    pub original_metadata: Option<Account<'info, MetadataAccount>>,
 
-    pub token_program: Program<'info, Token>,
     pub token_2022_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct AdminOnly<'info> {
+pub struct UpdateConfidentialMint<'info> {
     #[account(
-        mut, 
-        has_one = authority @ WrapperError::Unauthorized,
+        mut,
+        has_one = auditor_manager @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
         seeds = [b"config", wrapper_config.original_mint.as_ref()],
         bump = wrapper_config.bump,
     )]
     pub wrapper_config: Account<'info, WrapperConfig>,
-    pub authority: Signer<'info>,
+
+    pub auditor_manager: Signer<'info>,
+
+    #[account(mut)]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_2022_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawFees<'info> {
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
-
+pub struct ApproveConfidentialAccount<'info> {
     #[account(
-        has_one = authority @ WrapperError::Unauthorized,
-        has_one = original_mint @ WrapperError::InvalidMintPair,
-        has_one = vault,
-        seeds = [b"config", original_mint.key().as_ref()],
+        has_one = approver @ WrapperError::Unauthorized,
+        has_one = wrapped_mint,
+        seeds = [b"config", wrapper_config.original_mint.as_ref()],
         bump = wrapper_config.bump,
     )]
     pub wrapper_config: Account<'info, WrapperConfig>,
 
-    #[account(
-        seeds = [b"stats", original_mint.key().as_ref()],
-        bump = wrapper_stats.bump,
-    )]
-    pub wrapper_stats: Account<'info, WrapperStats>,
-
+    /// Pays for the approval record
     #[account(mut)]
-    pub vault: Account<'info, anchor_spl::token::TokenAccount>,
+    pub approver: Signer<'info>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
 
-    /// Authority's token account to receive fees
+    /// The token account to approve
     #[account(
         mut,
-        token::mint = original_mint,
-        token::authority = authority,
+        token::mint = wrapped_mint,
     )]
-    pub authority_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+    pub target_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        init,
+        payer = approver,
+        seeds = [b"approval", wrapped_mint.key().as_ref(), target_account.key().as_ref()],
+        bump,
+        space = ConfidentialApproval::LEN
+    )]
+    pub approval: Account<'info, ConfidentialApproval>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct FreezeAccountCtx<'info> {
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
+    pub original_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        has_one = authority @ WrapperError::Unauthorized,
+        has_one = freezer @ WrapperError::Unauthorized,
         has_one = original_mint @ WrapperError::InvalidMintPair,
         seeds = [b"config", original_mint.key().as_ref()],
         bump = wrapper_config.bump,
@@ -766,7 +3424,7 @@ pub struct FreezeAccountCtx<'info> {
     )]
     pub target_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub authority: Signer<'info>,
+    pub freezer: Signer<'info>,
     pub token_2022_program: Program<'info, Token2022>,
 }
 #[derive(Accounts)]
@@ -780,7 +3438,7 @@ pub struct ConfigureConfidentialAccount<'info> {
     )]
     pub wrapped_mint: InterfaceAccount<'info, Mint>,
 
-    pub original_mint: Account<'info, anchor_spl::token::Mint>,
+    pub original_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -789,6 +3447,9 @@ pub struct ConfigureConfidentialAccount<'info> {
     )]
     pub user_wrapped_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: Pubkey validity proof context state; owner and contents checked in handler
+    pub proof_context: Option<UncheckedAccount<'info>>,
+
     pub token_2022_program: Program<'info, Token2022>,
     /// CHECK: Instructions sysvar for ZK proof introspection
     #[account(address = anchor_lang::solana_program::sysvar::instructions::id())]
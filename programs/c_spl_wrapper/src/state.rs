@@ -1,39 +1,298 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::WrapperError;
+use crate::fees::{FeeCurrency, FeeRecipient, FeeSchedule, FeeTier, MAX_FEE_RECIPIENTS, MAX_FEE_TIERS};
+
+/// Program-wide settings, one per deployment
+#[account]
+pub struct ProgramConfig {
+    pub factory_admin: Pubkey,      // May initialize a wrapper for any mint
+    pub wrapper_count: u64,         // Wrappers registered so far
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    // 8 discriminator
+    // 32 factory_admin
+    // 8 wrapper_count
+    // 1 bump
+    // = 49 bytes
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+
+    /// Registry page the next wrapper is appended to
+    pub fn current_registry_page(&self) -> u64 {
+        self.wrapper_count / RegistryPage::CAPACITY as u64
+    }
+}
+
+/// Append-only list of WrapperConfig addresses, `CAPACITY` per page
+#[account]
+pub struct RegistryPage {
+    pub page: u64,                  // Page index (seed)
+    pub wrappers: Vec<Pubkey>,      // WrapperConfig PDAs in creation order
+    pub bump: u8,
+}
+
+impl RegistryPage {
+    // Sized so a full page fits in the 1024-byte return data of `list_wrappers`
+    pub const CAPACITY: usize = 30;
+
+    // 8 discriminator
+    // 8 page
+    // 4 + 32 * 30 wrappers (Vec<Pubkey>)
+    // 1 bump
+    // = 981 bytes
+    pub const LEN: usize = 8 + 8 + 4 + 32 * Self::CAPACITY + 1;
+}
+
+/// Who was allowed to create a wrapper
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitializerKind {
+    FactoryAdmin,
+    MintAuthority,
+}
+
 #[account]
 pub struct WrapperConfig {
-    pub authority: Pubkey,          // Admin who can pause/set fees
+    pub authority: Pubkey,          // Admin who grants/revokes roles
+    pub pending_authority: Option<Pubkey>, // Nominated authority awaiting acceptance
+    pub approver: Pubkey,           // Approves confidential accounts when auto-approve is off
+    pub pauser: Pubkey,             // Can pause/unpause
+    pub fee_manager: Pubkey,        // Can change fees
+    pub treasurer: Pubkey,          // Can withdraw fees
+    pub freezer: Pubkey,            // Can freeze/thaw wrapped accounts
+    pub auditor_manager: Pubkey,    // Can rotate the auditor key / toggle auto-approve
+    pub guardian: Pubkey,           // Can pause but never unpause (default = none)
+    pub initialized_by: InitializerKind, // Whether the factory admin or mint authority created it
     pub original_mint: Pubkey,      // The source SPL token mint
     pub wrapped_mint: Pubkey,       // The Token-2022 CT-enabled mint
     pub vault: Pubkey,              // The vault holding original tokens
     pub auditor_elgamal_pubkey: Option<[u8; 32]>, // Auditor's ElGamal key
+    pub auto_approve_new_accounts: bool, // Mirrors the wrapped mint's CT setting
     pub wrap_fee_bps: u16,          // Fee for wrapping (basis points)
     pub unwrap_fee_bps: u16,        // Fee for unwrapping (basis points)
-    pub is_paused: bool,            // Emergency pause state
+    pub fee_schedule: FeeSchedule,  // Flat fee, min/max and rounding for both directions
+    pub fee_tiers: Vec<FeeTier>,    // Volume discounts, ascending by min_volume
+    pub referral_share_bps: u16,    // Share of each fee credited to a referrer
+    pub fee_currency: FeeCurrency,  // Keep fees as original or wrapped tokens
+    pub fee_treasury: Pubkey,       // Wrapped-token account receiving fees in Wrapped mode
+    pub wrap_paused: bool,          // Blocks wrap / wrap_sol / wrap_and_deposit
+    pub unwrap_paused: bool,        // Blocks unwrap / unwrap_sol / unwrap_confidential
+    pub timelock_delay: i64,        // Seconds a queued admin action waits before execution
+    pub next_action_id: u64,        // Seed for the next TimelockedAction PDA
     pub bump: u8,                   // Bump seed for PDA
+    pub metadata_prefix: String,    // Prepended to the original's name/symbol (e.g. "c-")
 }
 
 impl WrapperConfig {
     // 8 discriminator
     // 32 authority
+    // 1 + 32 pending_authority (Option<Pubkey>)
+    // 32 approver
+    // 32 * 5 pauser, fee_manager, treasurer, freezer, auditor_manager
+    // 32 guardian
+    // 1 initialized_by
     // 32 original_mint
     // 32 wrapped_mint
     // 32 vault
     // 1 + 32 (Option<Pubkey>)
+    // 1 auto_approve_new_accounts
     // 2 wrap_fee_bps
     // 2 unwrap_fee_bps
-    // 1 is_paused
+    // 25 fee_schedule (FeeSchedule::LEN)
+    // 4 + 10 * 4 fee_tiers (Vec<FeeTier>, max 4)
+    // 2 referral_share_bps
+    // 1 fee_currency
+    // 32 fee_treasury
+    // 1 wrap_paused
+    // 1 unwrap_paused
+    // 8 timelock_delay
+    // 8 next_action_id
     // 1 bump
-    // = 8 + 32 * 4 + 33 + 2 + 2 + 1 + 1 = 175 bytes
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 33 + 2 + 2 + 1 + 1;
+    // 4 + 8 metadata_prefix (String, max 8 bytes)
+    // = 8 + 32 * 12 + 1 + 33 * 2 + 1 + 2 + 2 + 25 + 44 + 2 + 1 + 1 + 1 + 8 + 8 + 1 + 12 = 567 bytes
+    pub const LEN: usize = 8 + 32 + 33 + 32 + 32 * 5 + 32 + 1 + 32 + 32 + 32 + 33 + 1 + 2 + 2 + FeeSchedule::LEN + 4 + FeeTier::LEN * MAX_FEE_TIERS + 2 + 1 + 32 + 1 + 1 + 8 + 8 + 1 + 4 + Self::MAX_METADATA_PREFIX_LEN;
+
+    pub const MAX_METADATA_PREFIX_LEN: usize = 8;
+
+    /// Upper bound on `timelock_delay` (30 days)
+    pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+    /// Current holder of `role` (`Pubkey::default()` if revoked)
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Pauser => self.pauser,
+            Role::FeeManager => self.fee_manager,
+            Role::Treasurer => self.treasurer,
+            Role::Freezer => self.freezer,
+            Role::AuditorManager => self.auditor_manager,
+            Role::Approver => self.approver,
+        }
+    }
+
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::Pauser => self.pauser = holder,
+            Role::FeeManager => self.fee_manager = holder,
+            Role::Treasurer => self.treasurer = holder,
+            Role::Freezer => self.freezer = holder,
+            Role::AuditorManager => self.auditor_manager = holder,
+            Role::Approver => self.approver = holder,
+        }
+    }
+}
+
+/// Administrative roles, each held by a single key per wrapper
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Pauser,
+    FeeManager,
+    Treasurer,
+    Freezer,
+    AuditorManager,
+    Approver,
+}
+
+/// Which direction a pause change applies to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseDirection {
+    Wrap,
+    Unwrap,
+    Both,
+}
+
+/// Admin change that must wait out the timelock before it takes effect
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    SetFees { wrap_fee_bps: u16, unwrap_fee_bps: u16 },
+    SetFeeSchedule { fee_schedule: FeeSchedule },
+    ProposeAuthority { new_authority: Pubkey },
+    SetAuditor { auditor_elgamal_pubkey: Option<[u8; 32]> },
+    Unpause { direction: PauseDirection, resume_transfers: bool },
+    SetTimelockDelay { timelock_delay: i64 },
+    SetFeeTiers { fee_tiers: Vec<FeeTier> },
+    RevokeFeeExemption { user: Pubkey },
+    ClearFeeSplit,
+}
+
+impl AdminAction {
+    // 1 variant tag + 44 largest payload (SetFeeTiers: 4 + 10 * 4)
+    pub const MAX_LEN: usize = 1 + 4 + FeeTier::LEN * MAX_FEE_TIERS;
+
+    /// Key allowed to queue this action
+    pub fn proposer(&self, config: &WrapperConfig) -> Pubkey {
+        match self {
+            AdminAction::SetFees { .. }
+            | AdminAction::SetFeeSchedule { .. }
+            | AdminAction::SetFeeTiers { .. }
+            | AdminAction::RevokeFeeExemption { .. } => config.fee_manager,
+            AdminAction::ProposeAuthority { .. } => config.authority,
+            AdminAction::SetAuditor { .. } => config.auditor_manager,
+            AdminAction::Unpause { .. } => config.pauser,
+            AdminAction::SetTimelockDelay { .. } => config.authority,
+            AdminAction::ClearFeeSplit => config.treasurer,
+        }
+    }
+}
+
+#[account]
+pub struct TimelockedAction {
+    pub wrapper_config: Pubkey,  // Wrapper the action applies to
+    pub id: u64,                 // Sequence number from WrapperConfig::next_action_id
+    pub proposer: Pubkey,        // Role holder who queued it (receives rent back)
+    pub action: AdminAction,     // Change to apply
+    pub eta: i64,                // Earliest unix timestamp it can be executed
+    pub bump: u8,
+}
+
+impl TimelockedAction {
+    // 8 discriminator
+    // 32 wrapper_config
+    // 8 id
+    // 32 proposer
+    // 45 action (AdminAction::MAX_LEN)
+    // 8 eta
+    // 1 bump
+    // = 8 + 32 + 8 + 32 + 45 + 8 + 1 = 134 bytes
+    pub const LEN: usize = 8 + 32 + 8 + 32 + AdminAction::MAX_LEN + 8 + 1;
+}
+
+/// Per-user, per-wrapper volume used for fee tiers, plus the exemption flag
+#[account]
+pub struct UserVolume {
+    pub wrapper_config: Pubkey,
+    pub user: Pubkey,
+    pub cumulative_volume: u64,  // Original-token units wrapped + unwrapped
+    pub fee_exempt: bool,        // Set by the fee manager
+    pub bump: u8,
+}
+
+impl UserVolume {
+    // 8 discriminator
+    // 32 wrapper_config
+    // 32 user
+    // 8 cumulative_volume
+    // 1 fee_exempt
+    // 1 bump
+    // = 8 + 32 + 32 + 8 + 1 + 1 = 82 bytes
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 1;
+
+    /// Add `amount` to the running total, filling in the PDA fields on first use
+    pub fn record(&mut self, wrapper_config: Pubkey, user: Pubkey, bump: u8, amount: u64) -> Result<()> {
+        self.wrapper_config = wrapper_config;
+        self.user = user;
+        self.bump = bump;
+        self.cumulative_volume = self.cumulative_volume.checked_add(amount).ok_or(WrapperError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// How `distribute_fees` divides collected fees
+#[account]
+pub struct FeeSplit {
+    pub wrapper_config: Pubkey,
+    pub recipients: Vec<FeeRecipient>, // Paid in order; last one absorbs rounding dust
+    pub bump: u8,
+}
+
+impl FeeSplit {
+    // 8 discriminator
+    // 32 wrapper_config
+    // 4 + 34 * 4 recipients (Vec<FeeRecipient>, max 4)
+    // 1 bump
+    // = 8 + 32 + 140 + 1 = 181 bytes
+    pub const LEN: usize = 8 + 32 + 4 + FeeRecipient::LEN * MAX_FEE_RECIPIENTS + 1;
+}
+
+/// Referral fees owed to one referrer of one wrapper
+#[account]
+pub struct ReferrerBalance {
+    pub wrapper_config: Pubkey,
+    pub referrer: Pubkey,
+    pub accrued: u64,            // Claimable, in original-token units
+    pub bump: u8,
+}
+
+impl ReferrerBalance {
+    // 8 discriminator
+    // 32 wrapper_config
+    // 32 referrer
+    // 8 accrued
+    // 1 bump
+    // = 8 + 32 + 32 + 8 + 1 = 81 bytes
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
 }
 
 #[account]
 pub struct WrapperStats {
     pub total_wrapped: u64,      // Net tokens minted (after fees)
     pub total_unwrapped: u64,    // Net tokens redeemed (before fees)
-    pub total_deposited: u64,    // Gross tokens deposited to vault
+    pub total_deposited: u64,    // Gross tokens received by the vault
     pub total_fees_collected: u64,
+    pub total_transfer_fees: u64,  // Withheld by the original mint, not ours
+    pub referral_fees_owed: u64,   // Credited to referrers, still in the vault
+    pub fees_accrued: u64,         // Original-token fees collected but not yet withdrawn
+    pub wrapped_fees_collected: u64, // Fees paid straight to the treasury as wrapped tokens
     pub bump: u8,
 }
 
@@ -43,7 +302,46 @@ impl WrapperStats {
     // 8 total_unwrapped
     // 8 total_deposited
     // 8 total_fees_collected
+    // 8 total_transfer_fees
+    // 8 referral_fees_owed
+    // 8 fees_accrued
+    // 8 wrapped_fees_collected
+    // 1 bump
+    // = 8 + 8 * 8 + 1 = 73 bytes
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Book the protocol's part of a fee in the ledger for `fee_currency`
+    pub fn accrue_fee(&mut self, fee_currency: FeeCurrency, amount: u64) -> Result<()> {
+        match fee_currency {
+            FeeCurrency::Original => {
+                self.fees_accrued = self.fees_accrued.checked_add(amount).ok_or(WrapperError::Overflow)?;
+            }
+            FeeCurrency::Wrapped => {
+                self.wrapped_fees_collected = self.wrapped_fees_collected.checked_add(amount).ok_or(WrapperError::Overflow)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ConfidentialApproval {
+    pub wrapped_mint: Pubkey,    // Wrapped mint the account belongs to
+    pub token_account: Pubkey,   // Approved token account
+    pub owner: Pubkey,           // Owner of the token account at approval time
+    pub approved_by: Pubkey,     // Approver who signed
+    pub approved_at: i64,        // Unix timestamp of approval
+    pub bump: u8,
+}
+
+impl ConfidentialApproval {
+    // 8 discriminator
+    // 32 wrapped_mint
+    // 32 token_account
+    // 32 owner
+    // 32 approved_by
+    // 8 approved_at
     // 1 bump
-    // = 8 + 8 + 8 + 8 + 8 + 1 = 41 bytes
-    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 1;
+    // = 8 + 32 * 4 + 8 + 1 = 145 bytes
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
 }
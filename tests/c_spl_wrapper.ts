@@ -9,6 +9,17 @@ import {
   mintTo,
   getAccount,
   getMint,
+  getTokenMetadata,
+  transfer,
+  transferChecked,
+  getAssociatedTokenAddressSync,
+  NATIVE_MINT,
+  ExtensionType,
+  getExtensionData,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createReallocateInstruction,
   ASSOCIATED_TOKEN_PROGRAM_ID
 } from "@solana/spl-token";
 import { assert } from "chai";
@@ -24,6 +35,8 @@ describe("c-spl-wrapper", () => {
   let wrapperConfig: anchor.web3.PublicKey;
   let wrapperStats: anchor.web3.PublicKey;
   let vault: anchor.web3.PublicKey;
+  let userVolume: anchor.web3.PublicKey;
+  let feeSplit: anchor.web3.PublicKey;
 
   const user = (provider.wallet as anchor.Wallet).payer;
   let userOriginalAccount: anchor.web3.PublicKey;
@@ -32,6 +45,212 @@ describe("c-spl-wrapper", () => {
   const decimals = 6;
   const wrapAmount = new anchor.BN(1_000_000);
   const unwrapAmount = new anchor.BN(500_000);
+  const noFeeLimit = new anchor.BN("18446744073709551615"); // u64::MAX, accept any fee
+
+  // Pubkey validity proof (context pubkey + proof) for a fixed ElGamal key, generated offline
+  // with solana-zk-sdk. It only covers the pubkey, so every test account can reuse it.
+  const pubkeyValidityProof = Buffer.from(
+    "5e60bf946905bb1b0e85c2706ebf17215c830079fac9772b60871eea875a0639" +
+    "160284f3f467cfc406cb4b38b64187bc19f8062bb69a72468488b8f00f9c2333" +
+    "cbfde53b90cbec7d67dbe418396e1411e9def226f720b1577bcf20cb90d5d900",
+    "hex"
+  );
+  const elgamalPubkey = pubkeyValidityProof.subarray(0, 32);
+  // Authenticated encryption of a zero balance, the decryptable balance a new account starts with
+  const decryptableZeroBalance = Buffer.from("87295e4f7c0cd059ab28d6a92b8d7797e3399231b10317af0c05808e078043eb3c8fc5e5", "hex");
+  const zkElGamalProofProgramId = new anchor.web3.PublicKey("ZkE1Gama1Proof11111111111111111111111111111");
+
+  // Accounts shared by wrap/unwrap and their variants; no referrer, fees stay in the vault
+  const wrapAccounts = (overrides: Record<string, any> = {}) => ({
+    user: user.publicKey,
+    originalMint: originalMint,
+    wrapperConfig: wrapperConfig,
+    wrapperStats: wrapperStats,
+    userVolume: userVolume,
+    referrerBalance: null,
+    feeTreasury: null,
+    wrappedMint: wrappedMint,
+    userOriginalAccount: userOriginalAccount,
+    vault: vault,
+    userWrappedAccount: userWrappedAccount,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    token2022Program: TOKEN_2022_PROGRAM_ID,
+    systemProgram: anchor.web3.SystemProgram.programId,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    ...overrides,
+  });
+
+  // Address the next action queued on `config` will be stored at
+  const nextActionPda = async (config: anchor.web3.PublicKey) => {
+    const { nextActionId } = await program.account.wrapperConfig.fetch(config);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("action"), config.toBuffer(), nextActionId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
+  type ActionTarget = {
+    wrapperConfig: anchor.web3.PublicKey;
+    wrappedMint: anchor.web3.PublicKey;
+    userVolume?: anchor.web3.PublicKey;
+    feeSplit?: anchor.web3.PublicKey;
+  };
+
+  const executeQueued = (timelockedAction: anchor.web3.PublicKey, target: ActionTarget) =>
+    program.methods.executeAction()
+      .accounts({
+        wrapperConfig: target.wrapperConfig,
+        timelockedAction: timelockedAction,
+        proposer: user.publicKey,
+        executor: user.publicKey,
+        wrappedMint: target.wrappedMint,
+        userVolume: target.userVolume ?? null,
+        feeSplit: target.feeSplit ?? null,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+  // Queue an admin action and execute it straight away (wrappers use a 0s timelock)
+  const queueAndExecute = async (action: any, target: ActionTarget = { wrapperConfig, wrappedMint }) => {
+    const timelockedAction = await nextActionPda(target.wrapperConfig);
+    await program.methods.queueAction(action)
+      .accounts({
+        wrapperConfig: target.wrapperConfig,
+        timelockedAction: timelockedAction,
+        proposer: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await executeQueued(timelockedAction, target);
+  };
+
+  // Config, stats, wrapped mint and vault PDAs of the wrapper for `mint`
+  const wrapperPdas = (mint: anchor.web3.PublicKey) => {
+    const pda = (seed: string) =>
+      anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(seed), mint.toBuffer()], program.programId)[0];
+    return {
+      wrapperConfig: pda("config"),
+      wrapperStats: pda("stats"),
+      wrappedMint: pda("mint"),
+      vault: pda("vault"),
+    };
+  };
+
+  // Create a fee-free wrapper for another mint once the program config exists
+  const initializeWrapper = async (
+    mint: anchor.web3.PublicKey,
+    tokenProgram: anchor.web3.PublicKey = TOKEN_PROGRAM_ID,
+    caller: anchor.web3.Keypair = user
+  ) => {
+    const [programConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("program_config")],
+      program.programId
+    );
+    const { wrapperCount } = await program.account.programConfig.fetch(programConfig);
+    const [registryPage] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("registry"), wrapperCount.divn(30).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pdas = wrapperPdas(mint);
+
+    await program.methods.initialize(0, 0, null, true, "c-", new anchor.BN(0))
+      .accounts({
+        user: caller.publicKey,
+        programConfig: programConfig,
+        registryPage: registryPage,
+        originalMint: mint,
+        ...pdas,
+        originalMetadata: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: tokenProgram,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers(caller === user ? [] : [caller])
+      .rpc();
+    return pdas;
+  };
+
+  // wrapAccounts for the user on another wrapper created by initializeWrapper
+  const wrapAccountsFor = (
+    mint: anchor.web3.PublicKey,
+    pdas: ReturnType<typeof wrapperPdas>,
+    tokenProgram: anchor.web3.PublicKey,
+    originalAccount: anchor.web3.PublicKey
+  ) => wrapAccounts({
+    originalMint: mint,
+    ...pdas,
+    userVolume: anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_volume"), pdas.wrapperConfig.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    )[0],
+    userOriginalAccount: originalAccount,
+    userWrappedAccount: getAssociatedTokenAddressSync(pdas.wrappedMint, user.publicKey, false, TOKEN_2022_PROGRAM_ID),
+    tokenProgram: tokenProgram,
+  });
+
+  // ConfidentialTransferMint of a wrapped mint: authority (32), auto-approve flag (1), auditor ElGamal key (32)
+  const confidentialMintOf = async (mint: anchor.web3.PublicKey) => {
+    const info = await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID);
+    const data = getExtensionData(ExtensionType.ConfidentialTransferMint, info.tlvData)!;
+    return { autoApprove: data[32] === 1, auditor: Buffer.from(data.subarray(33, 65)) };
+  };
+
+  // ZK ElGamal proof program instruction verifying `pubkeyValidityProof` (VerifyPubkeyValidity = 4)
+  const verifyPubkeyValidityIx = () =>
+    new anchor.web3.TransactionInstruction({
+      programId: zkElGamalProofProgramId,
+      keys: [],
+      data: Buffer.concat([Buffer.from([4]), pubkeyValidityProof]),
+    });
+
+  // configure_confidential_account for the user's `account` with the fixed ElGamal key. The account
+  // is first given room for the extension; the proof instruction follows at `proofOffset` unless omitted.
+  const configureConfidential = (
+    mint: anchor.web3.PublicKey,
+    pdas: { wrappedMint: anchor.web3.PublicKey },
+    account: anchor.web3.PublicKey,
+    { proofOffset = 1, withProof = true }: { proofOffset?: number; withProof?: boolean } = {}
+  ) =>
+    program.methods.configureConfidentialAccount(
+      Array.from(elgamalPubkey),
+      Array.from(decryptableZeroBalance),
+      new anchor.BN(65_536),
+      proofOffset
+    )
+      .accounts({
+        user: user.publicKey,
+        wrappedMint: pdas.wrappedMint,
+        originalMint: mint,
+        userWrappedAccount: account,
+        proofContext: null,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([
+        createReallocateInstruction(
+          account,
+          user.publicKey,
+          [ExtensionType.ConfidentialTransferAccount],
+          user.publicKey,
+          [],
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ])
+      .postInstructions(withProof ? [verifyPubkeyValidityIx()] : [])
+      .rpc();
+
+  // ConfidentialTransferAccount of a token account: approved flag (1), ElGamal key (32), pending lo/hi,
+  // available and decryptable balances (64 * 3 + 36), two credit flags (2), pending credit counter (8)
+  const confidentialAccountOf = async (account: anchor.web3.PublicKey) => {
+    const info = await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID);
+    const data = getExtensionData(ExtensionType.ConfidentialTransferAccount, info.tlvData)!;
+    return {
+      approved: data[0] === 1,
+      elgamalPubkey: Buffer.from(data.subarray(1, 33)),
+      pendingBalanceCreditCounter: data.readBigUInt64LE(263),
+    };
+  };
 
   it("Initialized environment", async () => {
     originalMint = await createMint(
@@ -91,14 +310,43 @@ describe("c-spl-wrapper", () => {
     );
     vault = vaultPda;
 
-    await program.methods.initialize(0, 0, null) // 0 fees, no auditor
+    // Program-wide config; only the upgrade authority can create it
+    const [programConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("program_config")],
+      program.programId
+    );
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await program.methods.initializeProgramConfig(user.publicKey)
+      .accounts({
+        payer: user.publicKey,
+        programConfig: programConfig,
+        program: program.programId,
+        programData: programData,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // First registry page (wrapper #0)
+    const [registryPage] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("registry"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods.initialize(0, 0, null, true, "c-", new anchor.BN(0)) // 0 fees, no auditor, auto-approve, no timelock
       .accounts({
         user: user.publicKey,
+        programConfig: programConfig,
+        registryPage: registryPage,
         originalMint: originalMint,
         wrapperConfig: wrapperConfig,
         wrapperStats: wrapperStats,
         wrappedMint: wrappedMint,
         vault: vault,
+        originalMetadata: null, // no Metaplex metadata on the test mint
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         token2022Program: TOKEN_2022_PROGRAM_ID,
@@ -108,9 +356,82 @@ describe("c-spl-wrapper", () => {
 
     const mintInfo = await getMint(provider.connection, wrappedMint, undefined, TOKEN_2022_PROGRAM_ID);
     assert.ok(mintInfo.isInitialized);
+
+    // No Metaplex metadata: prefix plus a shortened original mint address
+    const mintAddress = originalMint.toBase58();
+    const metadata = await getTokenMetadata(provider.connection, wrappedMint);
+    assert.equal(metadata.name, `c-${mintAddress.slice(0, 4)}..${mintAddress.slice(-4)}`);
+    assert.equal(metadata.symbol, `c-${mintAddress.slice(0, 4)}`);
+
+    const config = await program.account.wrapperConfig.fetch(wrapperConfig);
+    assert.deepEqual(config.initializedBy, { mintAuthority: {} });
+
+    const registered = await program.methods.listWrappers(new anchor.BN(0))
+      .accounts({ registryPage: registryPage })
+      .view();
+    assert.ok(registered.some((k: anchor.web3.PublicKey) => k.equals(wrapperConfig)));
+
+    [userVolume] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_volume"), wrapperConfig.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    [feeSplit] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_split"), wrapperConfig.toBuffer()],
+      program.programId
+    );
     console.log("Wrapper Config:", wrapperConfig.toBase58());
   });
 
+  it("Factory: Only The Factory Admin Or Mint Authority Can Initialize", async () => {
+    // A mint whose authority is neither the caller nor the factory admin
+    const mint = await createMint(provider.connection, user, anchor.web3.Keypair.generate().publicKey, null, decimals);
+    const stranger = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: user.publicKey,
+        toPubkey: stranger.publicKey,
+        lamports: anchor.web3.LAMPORTS_PER_SOL,
+      })
+    ));
+
+    try {
+      await initializeWrapper(mint, TOKEN_PROGRAM_ID, stranger);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "UnauthorizedInitializer");
+    }
+
+    // The factory admin may wrap any mint
+    const pdas = await initializeWrapper(mint);
+    const config = await program.account.wrapperConfig.fetch(pdas.wrapperConfig);
+    assert.deepEqual(config.initializedBy, { factoryAdmin: {} });
+  });
+
+  it("Admin: Sync Metadata Falls Back To The Shortened Address", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const other = await initializeWrapper(mint);
+
+    // No Metaplex metadata for this mint; a new prefix still applies
+    await program.methods.syncMetadata("w-")
+      .accounts({
+        wrapperConfig: other.wrapperConfig,
+        authority: user.publicKey,
+        wrappedMint: other.wrappedMint,
+        originalMetadata: null,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const mintAddress = mint.toBase58();
+    const metadata = await getTokenMetadata(provider.connection, other.wrappedMint);
+    assert.equal(metadata.name, `w-${mintAddress.slice(0, 4)}..${mintAddress.slice(-4)}`);
+    assert.equal(metadata.symbol, `w-${mintAddress.slice(0, 4)}`);
+    assert.equal(metadata.uri, "");
+    const config = await program.account.wrapperConfig.fetch(other.wrapperConfig);
+    assert.equal(config.metadataPrefix, "w-");
+  });
+
   it("Wraps Tokens", async () => {
     userWrappedAccount = (await getOrCreateAssociatedTokenAccount(
       provider.connection,
@@ -123,21 +444,8 @@ describe("c-spl-wrapper", () => {
       TOKEN_2022_PROGRAM_ID
     )).address;
 
-    await program.methods.wrap(wrapAmount)
-      .accounts({
-        user: user.publicKey,
-        originalMint: originalMint,
-        wrapperConfig: wrapperConfig,
-        wrapperStats: wrapperStats,
-        wrappedMint: wrappedMint,
-        userOriginalAccount: userOriginalAccount,
-        vault: vault,
-        userWrappedAccount: userWrappedAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      })
+    await program.methods.wrap(wrapAmount, noFeeLimit, null)
+      .accounts(wrapAccounts())
       .rpc();
 
     const userWrappedInfo = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
@@ -146,21 +454,8 @@ describe("c-spl-wrapper", () => {
   });
 
   it("Unwraps Tokens", async () => {
-    await program.methods.unwrap(unwrapAmount)
-      .accounts({
-        user: user.publicKey,
-        originalMint: originalMint,
-        wrapperConfig: wrapperConfig,
-        wrapperStats: wrapperStats,
-        wrappedMint: wrappedMint,
-        userOriginalAccount: userOriginalAccount,
-        vault: vault,
-        userWrappedAccount: userWrappedAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      })
+    await program.methods.unwrap(unwrapAmount, noFeeLimit, null)
+      .accounts(wrapAccounts())
       .rpc();
 
     const userWrappedInfo = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
@@ -168,12 +463,7 @@ describe("c-spl-wrapper", () => {
   });
 
   it("Admin: Can Set Fees", async () => {
-    await program.methods.setFees(100, 100) // 1%
-      .accounts({
-        wrapperConfig: wrapperConfig,
-        authority: user.publicKey,
-      })
-      .rpc();
+    await queueAndExecute({ setFees: { wrapFeeBps: 100, unwrapFeeBps: 100 } }); // 1%
 
     // Fetch config account to verify
     // Since we don't have types generated yet, manual fetch or trust RPC no error
@@ -181,30 +471,19 @@ describe("c-spl-wrapper", () => {
   });
 
   it("Admin: Can Pause and Unpause", async () => {
-    await program.methods.pause()
+    await program.methods.pause(false) // wrap/unwrap only, token transfers stay live
       .accounts({
         wrapperConfig: wrapperConfig,
-        authority: user.publicKey,
+        pauser: user.publicKey,
+        wrappedMint: wrappedMint,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
       })
       .rpc();
 
     // Verify Pause works (Wrap should fail)
     try {
-      await program.methods.wrap(new anchor.BN(100))
-        .accounts({
-          user: user.publicKey,
-          originalMint: originalMint,
-          wrapperConfig: wrapperConfig,
-          wrapperStats: wrapperStats,
-          wrappedMint: wrappedMint,
-          userOriginalAccount: userOriginalAccount,
-          vault: vault,
-          userWrappedAccount: userWrappedAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          token2022Program: TOKEN_2022_PROGRAM_ID,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        })
+      await program.methods.wrap(new anchor.BN(100), noFeeLimit, null)
+        .accounts(wrapAccounts())
         .rpc();
       assert.fail("Should have failed");
     } catch (e) {
@@ -212,64 +491,1169 @@ describe("c-spl-wrapper", () => {
       assert.ok(true);
     }
 
-    await program.methods.unpause()
+    await queueAndExecute({ unpause: { direction: { both: {} }, resumeTransfers: false } });
+
+    // Verify Unpause (Wrap should work)
+    await program.methods.wrap(new anchor.BN(100), noFeeLimit, null)
+      .accounts(wrapAccounts())
+      .rpc();
+    // After the 1% setFees action, wrapping 100 tokens = 99 net
+    // Previous balance was 500_000 (after unwrap), so new balance = 500_000 + 99 = 500_099
+    const finalBalance = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    // Note: exact assertion depends on previous test state
+    // For now, just verify success (no error thrown)
+    console.log("Final wrapped balance:", finalBalance.amount.toString());
+  });
+
+  it("Admin: Can Pause Wrap While Unwrap Stays Open", async () => {
+    await program.methods.pauseWrap()
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        pauser: user.publicKey,
+      })
+      .rpc();
+
+    const accounts = wrapAccounts();
+
+    // Wrap should fail
+    try {
+      await program.methods.wrap(new anchor.BN(100), noFeeLimit, null).accounts(accounts).rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "WrapPaused");
+    }
+
+    // Unwrap should still work
+    await program.methods.unwrap(new anchor.BN(100), noFeeLimit, null).accounts(accounts).rpc();
+
+    await queueAndExecute({ unpause: { direction: { wrap: {} }, resumeTransfers: false } });
+  });
+
+  it("Admin: Pausing Transfers Halts The Wrapped Token Until Resumed", async () => {
+    const recipientAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      wrappedMint,
+      anchor.web3.Keypair.generate().publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    const sendWrapped = () =>
+      transferChecked(
+        provider.connection,
+        user,
+        userWrappedAccount,
+        wrappedMint,
+        recipientAccount,
+        user,
+        1,
+        decimals,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    await program.methods.pause(true) // also pause the mint through the Pausable extension
       .accounts({
         wrapperConfig: wrapperConfig,
-        authority: user.publicKey,
+        pauser: user.publicKey,
+        wrappedMint: wrappedMint,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
       })
       .rpc();
 
-    // Verify Unpause (Wrap should work)
-    await program.methods.wrap(new anchor.BN(100))
+    try {
+      await sendWrapped();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.notEqual(e.message, "Should have failed");
+    }
+
+    await queueAndExecute({ unpause: { direction: { both: {} }, resumeTransfers: true } });
+
+    await sendWrapped();
+    const received = await getAccount(provider.connection, recipientAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(received.amount.toString(), "1");
+    const config = await program.account.wrapperConfig.fetch(wrapperConfig);
+    assert.isFalse(config.wrapPaused);
+    assert.isFalse(config.unwrapPaused);
+  });
+
+  it("Admin: Unpausing Both Directions Resumes A Paused Mint", async () => {
+    await program.methods.pause(true)
       .accounts({
-        user: user.publicKey,
-        originalMint: originalMint,
         wrapperConfig: wrapperConfig,
-        wrapperStats: wrapperStats,
+        pauser: user.publicKey,
         wrappedMint: wrappedMint,
-        userOriginalAccount: userOriginalAccount,
-        vault: vault,
-        userWrappedAccount: userWrappedAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
         token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    // Without resumeTransfers the mint still resumes once neither direction is paused,
+    // otherwise wraps would keep failing on the paused mint
+    await queueAndExecute({ unpause: { direction: { both: {} }, resumeTransfers: false } });
+
+    const before = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods.wrap(new anchor.BN(1_000), noFeeLimit, null).accounts(wrapAccounts()).rpc();
+    const after = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal((after.amount - before.amount).toString(), "990"); // 1% fee
+  });
+
+  it("Admin: Fee-Exempt Users Pay No Fee", async () => {
+    // Granting only lowers a fee, so it applies at once
+    await program.methods.grantFeeExemption(user.publicKey)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        userVolume: userVolume,
+        feeManager: user.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       })
-    // After setFees(100, 100) = 1% fee, wrapping 100 tokens = 99 net
-    // Previous balance was 500_000 (after unwrap), so new balance = 500_000 + 99 = 500_099
-    const finalBalance = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
-    // Note: exact assertion depends on previous test state
-    // For now, just verify success (no error thrown)
-    console.log("Final wrapped balance:", finalBalance.amount.toString());
+      .rpc();
+
+    const before = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null)
+      .accounts(wrapAccounts())
+      .rpc();
+    const after = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal((after.amount - before.amount).toString(), "10000");
+
+    // Revoking raises the user's fee, so it goes through the timelock and needs their volume account
+    try {
+      await queueAndExecute({ revokeFeeExemption: { user: user.publicKey } });
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidUserVolume");
+    }
+    await queueAndExecute({ revokeFeeExemption: { user: user.publicKey } }, { wrapperConfig, wrappedMint, userVolume });
+    assert.isFalse((await program.account.userVolume.fetch(userVolume)).feeExempt);
   });
 
-  it("Admin: Can Withdraw Fees", async () => {
-    // Create authority token account if needed
-    const authorityTokenAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection,
-      user,
-      originalMint,
-      user.publicKey
-    )).address;
+  it("Admin: Fee Schedule Applies Flat Fee, Bounds And Rounding", async () => {
+    const setSchedule = (schedule: { flatFee?: number; minFee?: number; maxFee?: number; roundUp?: boolean }) =>
+      queueAndExecute({
+        setFeeSchedule: {
+          feeSchedule: {
+            flatFee: new anchor.BN(schedule.flatFee ?? 0),
+            minFee: new anchor.BN(schedule.minFee ?? 0),
+            maxFee: new anchor.BN(schedule.maxFee ?? 0),
+            rounding: schedule.roundUp ? { up: {} } : { down: {} },
+          },
+        },
+      });
+
+    // Wrapped tokens received for wrapping `amount` at the 1% rate
+    const netOf = async (amount: number) => {
+      const before = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      await program.methods.wrap(new anchor.BN(amount), noFeeLimit, null).accounts(wrapAccounts()).rpc();
+      const after = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      return (after.amount - before.amount).toString();
+    };
+
+    await setSchedule({ flatFee: 5 });
+    assert.equal(await netOf(1_000), "985"); // 10 + 5 flat
+
+    await setSchedule({ minFee: 20 });
+    assert.equal(await netOf(1_000), "980"); // 10 raised to the minimum
+
+    await setSchedule({ maxFee: 3 });
+    assert.equal(await netOf(1_000), "997"); // 10 lowered to the maximum
+
+    await setSchedule({ roundUp: true });
+    assert.equal(await netOf(150), "148"); // 1.5 rounds up to 2
+
+    // The 10% cap is on the bps rate only: a minimum fee is charged in full
+    await setSchedule({ minFee: 50 });
+    assert.equal(await netOf(100), "50");
+
+    // Bounds are checked when the schedule is queued
+    for (const schedule of [{ minFee: 20, maxFee: 10 }, { flatFee: 20, maxFee: 10 }]) {
+      try {
+        await setSchedule(schedule);
+        assert.fail("Should have failed");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidFeeSchedule");
+      }
+    }
+
+    await setSchedule({});
+  });
+
+  it("Admin: Volume Tiers Discount The Fee", async () => {
+    const setTiers = (tiers: { minVolume: number; discountBps: number }[]) =>
+      queueAndExecute({
+        setFeeTiers: {
+          feeTiers: tiers.map((t) => ({ minVolume: new anchor.BN(t.minVolume), discountBps: t.discountBps })),
+        },
+      });
+
+    // Tiers must ascend by volume and discount at most 100%
+    for (const tiers of [
+      [{ minVolume: 2_000, discountBps: 1_000 }, { minVolume: 1_000, discountBps: 2_000 }],
+      [{ minVolume: 1_000, discountBps: 10_001 }],
+    ]) {
+      try {
+        await setTiers(tiers);
+        assert.fail("Should have failed");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidFeeTiers");
+      }
+    }
+
+    // The user's volume so far reaches the second tier: half of the 1% rate is waived
+    await setTiers([{ minVolume: 1, discountBps: 2_500 }, { minVolume: 1_000, discountBps: 5_000 }]);
+    const before = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(wrapAccounts()).rpc();
+    const after = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal((after.amount - before.amount).toString(), "9950");
+
+    await setTiers([]);
+  });
+
+  it("User: Fee Limit And Deadline Are Enforced", async () => {
+    const accounts = wrapAccounts();
+
+    // 1% of 10_000 is 100, above the caller's limit of 50
+    try {
+      await program.methods.wrap(new anchor.BN(10_000), new anchor.BN(50), null).accounts(accounts).rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "FeeExceedsLimit");
+    }
+
+    // A deadline in the past rejects the transaction
+    const past = new anchor.BN(Math.floor(Date.now() / 1000) - 3600);
+    try {
+      await program.methods.unwrap(new anchor.BN(10_000), noFeeLimit, past).accounts(accounts).rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "DeadlineExpired");
+    }
 
-    // Note: With 0 fees throughout most tests, there may be no fees to withdraw
-    // This test just verifies the instruction works (may fail with ZeroAmount if no fees)
+    await program.methods.wrap(new anchor.BN(10_000), new anchor.BN(100), null).accounts(accounts).rpc();
+  });
+
+  it("User: Can Wrap And Unwrap For An Exact Output", async () => {
+    const accounts = wrapAccounts();
+
+    // 9_900 net at 1% (rounded down) needs 9_999 in: 9_999 - 99 = 9_900
     try {
-      await program.methods.withdrawFees()
+      await program.methods.wrapExactOut(new anchor.BN(9_900), new anchor.BN(9_998), null).accounts(accounts).rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "MaxInputExceeded");
+    }
+
+    const before = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods.wrapExactOut(new anchor.BN(9_900), new anchor.BN(9_999), null).accounts(accounts).rpc();
+    const afterWrap = await getAccount(provider.connection, userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal((afterWrap.amount - before.amount).toString(), "9900");
+
+    const originalBefore = await getAccount(provider.connection, userOriginalAccount);
+    await program.methods.unwrapExactOut(new anchor.BN(9_900), new anchor.BN(9_999), null).accounts(accounts).rpc();
+    const originalAfter = await getAccount(provider.connection, userOriginalAccount);
+    assert.equal((originalAfter.amount - originalBefore.amount).toString(), "9900");
+  });
+
+  it("Partner: Referral Share Is Credited And Claimed", async () => {
+    const partner = anchor.web3.Keypair.generate();
+    const referrerBalanceOf = (referrer: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("referrer"), wrapperConfig.toBuffer(), referrer.toBuffer()],
+        program.programId
+      )[0];
+    const register = (referrer: anchor.web3.PublicKey) =>
+      program.methods.registerReferrer(referrer)
         .accounts({
-          originalMint: originalMint,
           wrapperConfig: wrapperConfig,
-          wrapperStats: wrapperStats,
-          vault: vault,
-          authority: user.publicKey,
-          authorityTokenAccount: authorityTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          referrerBalance: referrerBalanceOf(referrer),
+          feeManager: user.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
-      console.log("Fees withdrawn successfully");
+
+    await program.methods.setReferralShare(5_000) // half of each fee
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        feeManager: user.publicKey,
+      })
+      .rpc();
+    await register(partner.publicKey);
+    await register(user.publicKey);
+
+    // A user cannot collect a share of their own fee
+    try {
+      await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null)
+        .accounts(wrapAccounts({ referrerBalance: referrerBalanceOf(user.publicKey) }))
+        .rpc();
+      assert.fail("Should have failed");
     } catch (e: any) {
-      // Expected if no fees collected
-      console.log("No fees to withdraw (expected):", e.message?.slice(0, 50));
+      assert.include(e.message, "SelfReferral");
     }
+
+    // 1% of 10_000 = 100, half of it credited to the partner
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null)
+      .accounts(wrapAccounts({ referrerBalance: referrerBalanceOf(partner.publicKey) }))
+      .rpc();
+    const balance = await program.account.referrerBalance.fetch(referrerBalanceOf(partner.publicKey));
+    assert.equal(balance.accrued.toString(), "50");
+
+    const partnerTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      originalMint,
+      partner.publicKey
+    )).address;
+
+    await program.methods.claimReferralFees()
+      .accounts({
+        originalMint: originalMint,
+        wrapperConfig: wrapperConfig,
+        wrapperStats: wrapperStats,
+        referrerBalance: referrerBalanceOf(partner.publicKey),
+        vault: vault,
+        referrer: partner.publicKey,
+        referrerTokenAccount: partnerTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([partner])
+      .rpc();
+
+    const claimed = await getAccount(provider.connection, partnerTokenAccount);
+    assert.equal(claimed.amount.toString(), "50");
+    const stats = await program.account.wrapperStats.fetch(wrapperStats);
+    assert.equal(stats.referralFeesOwed.toString(), "0");
+  });
+
+  it("Treasurer: Fees Can Be Taken In Wrapped Tokens", async () => {
+    const treasury = anchor.web3.Keypair.generate();
+    const feeTreasury = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      wrappedMint,
+      treasury.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    const setFeeCurrency = (feeCurrency: any, treasuryAccount: anchor.web3.PublicKey | null) =>
+      program.methods.setFeeCurrency(feeCurrency)
+        .accounts({
+          wrapperConfig: wrapperConfig,
+          treasurer: user.publicKey,
+          wrappedMint: wrappedMint,
+          feeTreasury: treasuryAccount,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    const wrappedBalance = async (account: anchor.web3.PublicKey) =>
+      (await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID)).amount;
+
+    await setFeeCurrency({ wrapped: {} }, feeTreasury);
+    const config = await program.account.wrapperConfig.fetch(wrapperConfig);
+    assert.ok(config.feeTreasury.equals(feeTreasury));
+
+    // Wrapping without the treasury account is rejected
+    try {
+      await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(wrapAccounts()).rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidFeeTreasury");
+    }
+
+    const accounts = wrapAccounts({ feeTreasury: feeTreasury });
+    const statsBefore = await program.account.wrapperStats.fetch(wrapperStats);
+
+    // Wrap 10_000: 9_900 minted to the user, the 100 fee minted to the treasury
+    const userBefore = await wrappedBalance(userWrappedAccount);
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(accounts).rpc();
+    assert.equal((await wrappedBalance(userWrappedAccount) - userBefore).toString(), "9900");
+    assert.equal((await wrappedBalance(feeTreasury)).toString(), "100");
+
+    // Unwrap 10_000: the 100 fee moves to the treasury, only 9_900 is burned
+    const supplyBefore = (await getMint(provider.connection, wrappedMint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+    await program.methods.unwrap(new anchor.BN(10_000), noFeeLimit, null).accounts(accounts).rpc();
+    const supplyAfter = (await getMint(provider.connection, wrappedMint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+    assert.equal((supplyBefore - supplyAfter).toString(), "9900");
+    assert.equal((await wrappedBalance(feeTreasury)).toString(), "200");
+
+    // Minted and burned totals include the treasury's share; the vault accrues nothing
+    const stats = await program.account.wrapperStats.fetch(wrapperStats);
+    assert.equal(stats.totalWrapped.sub(statsBefore.totalWrapped).toString(), "10000");
+    assert.equal(stats.totalUnwrapped.sub(statsBefore.totalUnwrapped).toString(), "9900");
+    assert.equal(stats.wrappedFeesCollected.sub(statsBefore.wrappedFeesCollected).toString(), "200");
+    assert.equal(stats.feesAccrued.toString(), statsBefore.feesAccrued.toString());
+
+    await setFeeCurrency({ original: {} }, null);
+  });
+
+  it("User: Can Wrap And Unwrap SOL", async () => {
+    // The native mint has no mint authority, so the factory admin creates its wrapper
+    const sol = await initializeWrapper(NATIVE_MINT);
+    const [solUserVolume] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_volume"), sol.wrapperConfig.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [tempSolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_unwrap"), NATIVE_MINT.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const solUserWrapped = getAssociatedTokenAddressSync(sol.wrappedMint, user.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    const solAccounts = {
+      user: user.publicKey,
+      originalMint: NATIVE_MINT,
+      ...sol,
+      userVolume: solUserVolume,
+      referrerBalance: null,
+      feeTreasury: null,
+      userWrappedAccount: solUserWrapped,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const lamports = 100_000_000; // 0.1 SOL
+
+    await program.methods.wrapSol(new anchor.BN(lamports), noFeeLimit, null)
+      .accounts({ ...solAccounts, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID })
+      .rpc();
+    const wrapped = await getAccount(provider.connection, solUserWrapped, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(wrapped.amount.toString(), lamports.toString());
+    const vaultInfo = await getAccount(provider.connection, sol.vault);
+    assert.equal(vaultInfo.amount.toString(), lamports.toString());
+
+    const before = await provider.connection.getBalance(user.publicKey);
+    await program.methods.unwrapSol(new anchor.BN(lamports / 2), noFeeLimit, null)
+      .accounts({ ...solAccounts, tempSolAccount: tempSolAccount })
+      .rpc();
+    const after = await provider.connection.getBalance(user.publicKey);
+
+    // SOL comes back less the transaction fee; the temporary rent is refunded
+    assert.isAtMost(after - before, lamports / 2);
+    assert.isAbove(after - before, lamports / 2 - 10_000);
+    assert.isNull(await provider.connection.getAccountInfo(tempSolAccount));
+    const remaining = await getAccount(provider.connection, solUserWrapped, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(remaining.amount.toString(), (lamports / 2).toString());
+
+    // Fees and referral shares apply to SOL exactly as to other tokens
+    const partner = anchor.web3.Keypair.generate();
+    const [partnerBalance] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), sol.wrapperConfig.toBuffer(), partner.publicKey.toBuffer()],
+      program.programId
+    );
+    await queueAndExecute({ setFees: { wrapFeeBps: 100, unwrapFeeBps: 100 } }, sol); // 1%
+    await program.methods.setReferralShare(5_000)
+      .accounts({
+        wrapperConfig: sol.wrapperConfig,
+        feeManager: user.publicKey,
+      })
+      .rpc();
+    await program.methods.registerReferrer(partner.publicKey)
+      .accounts({
+        wrapperConfig: sol.wrapperConfig,
+        referrerBalance: partnerBalance,
+        feeManager: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // 1% of 10_000 = 100 on each side, half of it credited to the partner
+    await program.methods.wrapSol(new anchor.BN(10_000), noFeeLimit, null)
+      .accounts({ ...solAccounts, referrerBalance: partnerBalance, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID })
+      .rpc();
+    const afterWrap = await getAccount(provider.connection, solUserWrapped, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal((afterWrap.amount - remaining.amount).toString(), "9900");
+    assert.equal((await program.account.referrerBalance.fetch(partnerBalance)).accrued.toString(), "50");
+
+    await program.methods.unwrapSol(new anchor.BN(10_000), noFeeLimit, null)
+      .accounts({ ...solAccounts, referrerBalance: partnerBalance, tempSolAccount: tempSolAccount })
+      .rpc();
+    assert.equal((await program.account.referrerBalance.fetch(partnerBalance)).accrued.toString(), "100");
+    const stats = await program.account.wrapperStats.fetch(sol.wrapperStats);
+    assert.equal(stats.referralFeesOwed.toString(), "100");
+    assert.equal(stats.feesAccrued.toString(), "100");
+  });
+
+  it("User: Can Wrap And Unwrap A Token-2022 Mint", async () => {
+    const mint = await createMint(
+      provider.connection,
+      user,
+      user.publicKey,
+      null,
+      decimals,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const originalAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    await mintTo(provider.connection, user, mint, originalAccount, user.publicKey, 10_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const pdas = await initializeWrapper(mint, TOKEN_2022_PROGRAM_ID);
+    const accounts = wrapAccountsFor(mint, pdas, TOKEN_2022_PROGRAM_ID, originalAccount);
+
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(accounts).rpc();
+    const vaultInfo = await getAccount(provider.connection, pdas.vault, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(vaultInfo.amount.toString(), "10000");
+    const wrapped = await getAccount(provider.connection, accounts.userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(wrapped.amount.toString(), "10000");
+
+    await program.methods.unwrap(new anchor.BN(10_000), noFeeLimit, null).accounts(accounts).rpc();
+    const original = await getAccount(provider.connection, originalAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(original.amount.toString(), "10000");
+  });
+
+  it("User: Wrapping A Transfer-Fee Mint Mints What The Vault Received", async () => {
+    // Token-2022 mint withholding 1% on every transfer
+    const mintKeypair = anchor.web3.Keypair.generate();
+    const mint = mintKeypair.publicKey;
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const createMintTx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: user.publicKey,
+        newAccountPubkey: mint,
+        space: mintLen,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(mint, user.publicKey, user.publicKey, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+      createInitializeMintInstruction(mint, decimals, user.publicKey, null, TOKEN_2022_PROGRAM_ID)
+    );
+    await provider.sendAndConfirm(createMintTx, [mintKeypair]);
+
+    const originalAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    await mintTo(provider.connection, user, mint, originalAccount, user.publicKey, 10_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const pdas = await initializeWrapper(mint, TOKEN_2022_PROGRAM_ID);
+    const accounts = wrapAccountsFor(mint, pdas, TOKEN_2022_PROGRAM_ID, originalAccount);
+
+    // 100 of 10_000 is withheld on the way in; only the 9_900 received is minted
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(accounts).rpc();
+    const wrapped = await getAccount(provider.connection, accounts.userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(wrapped.amount.toString(), "9900");
+    let stats = await program.account.wrapperStats.fetch(pdas.wrapperStats);
+    assert.equal(stats.totalDeposited.toString(), "9900");
+    assert.equal(stats.totalTransferFees.toString(), "100");
+
+    // Unwrapping 9_900 sends 9_900 out of the vault, of which 99 is withheld
+    await program.methods.unwrap(new anchor.BN(9_900), noFeeLimit, null).accounts(accounts).rpc();
+    const original = await getAccount(provider.connection, originalAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(original.amount.toString(), "9801");
+    stats = await program.account.wrapperStats.fetch(pdas.wrapperStats);
+    assert.equal(stats.totalTransferFees.toString(), "199");
+  });
+
+  it("Guardian: Can Pause But Not Unpause", async () => {
+    const guardian = anchor.web3.Keypair.generate();
+
+    await program.methods.setGuardian(guardian.publicKey)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    await program.methods.guardianPause(false)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        guardian: guardian.publicKey,
+        wrappedMint: wrappedMint,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([guardian])
+      .rpc();
+
+    const config = await program.account.wrapperConfig.fetch(wrapperConfig);
+    assert.isTrue(config.wrapPaused);
+    assert.isTrue(config.unwrapPaused);
+
+    // Unpausing still goes through the pauser's timelocked action
+    await queueAndExecute({ unpause: { direction: { both: {} }, resumeTransfers: false } });
+  });
+
+  it("Admin: Role Holders Are Enforced", async () => {
+    const pauser = anchor.web3.Keypair.generate();
+
+    await program.methods.grantRole({ pauser: {} }, pauser.publicKey)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    // The previous holder can no longer pause
+    try {
+      await program.methods.pauseWrap()
+        .accounts({
+          wrapperConfig: wrapperConfig,
+          pauser: user.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+
+    await program.methods.revokeRole({ pauser: {} })
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    await program.methods.grantRole({ pauser: {} }, user.publicKey)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        authority: user.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Auditor Manager: Confidential Mint Settings Follow The Config", async () => {
+    const setAutoApprove = (enabled: boolean, signer: anchor.web3.Keypair = user) =>
+      program.methods.setAutoApprove(enabled)
+        .accounts({
+          wrapperConfig: wrapperConfig,
+          auditorManager: signer.publicKey,
+          wrappedMint: wrappedMint,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers(signer === user ? [] : [signer])
+        .rpc();
+
+    // Only the auditor manager can change the mint's confidential settings
+    try {
+      await setAutoApprove(false, anchor.web3.Keypair.generate());
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+    assert.isTrue((await confidentialMintOf(wrappedMint)).autoApprove);
+
+    await setAutoApprove(false);
+    assert.isFalse((await confidentialMintOf(wrappedMint)).autoApprove);
+    assert.isFalse((await program.account.wrapperConfig.fetch(wrapperConfig)).autoApproveNewAccounts);
+    await setAutoApprove(true);
+    assert.isTrue((await confidentialMintOf(wrappedMint)).autoApprove);
+
+    // Auditor rotation is timelocked and lands on the mint once executed
+    const auditor = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    await queueAndExecute({ setAuditor: { auditorElgamalPubkey: Array.from(auditor) } });
+    assert.isTrue((await confidentialMintOf(wrappedMint)).auditor.equals(auditor));
+
+    await queueAndExecute({ setAuditor: { auditorElgamalPubkey: null } });
+    assert.isTrue((await confidentialMintOf(wrappedMint)).auditor.equals(Buffer.alloc(32)));
+  });
+
+  it("User: Configuring A Confidential Account Needs The Proof At The Given Offset", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const other = await initializeWrapper(mint);
+    const account = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      other.wrappedMint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    // Offset 0 would point at the configure instruction itself
+    try {
+      await configureConfidential(mint, other, account, { proofOffset: 0 });
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidProof");
+    }
+
+    // -1 points at the reallocate instruction, not the proof
+    try {
+      await configureConfidential(mint, other, account, { proofOffset: -1 });
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidProof");
+    }
+
+    // No proof instruction in the transaction at all
+    try {
+      await configureConfidential(mint, other, account, { withProof: false });
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.notEqual(e.message, "Should have failed");
+    }
+
+    const info = await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.isNull(getExtensionData(ExtensionType.ConfidentialTransferAccount, info.tlvData));
+
+    await configureConfidential(mint, other, account);
+    assert.isTrue((await confidentialAccountOf(account)).elgamalPubkey.equals(elgamalPubkey));
+  });
+
+  it("Approver: Approves Confidential Accounts When Auto-Approve Is Off", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const other = await initializeWrapper(mint);
+    await program.methods.setAutoApprove(false)
+      .accounts({
+        wrapperConfig: other.wrapperConfig,
+        auditorManager: user.publicKey,
+        wrappedMint: other.wrappedMint,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const account = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      other.wrappedMint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    await configureConfidential(mint, other, account);
+    const configured = await confidentialAccountOf(account);
+    assert.isFalse(configured.approved);
+    assert.isTrue(configured.elgamalPubkey.equals(elgamalPubkey));
+
+    const [approval] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("approval"), other.wrappedMint.toBuffer(), account.toBuffer()],
+      program.programId
+    );
+    const approve = (signer: anchor.web3.Keypair) =>
+      program.methods.approveConfidentialAccount()
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          approver: signer.publicKey,
+          wrappedMint: other.wrappedMint,
+          targetAccount: account,
+          approval: approval,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers(signer === user ? [] : [signer])
+        .rpc();
+
+    // Only the approver role can approve
+    const stranger = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    try {
+      await approve(stranger);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+    assert.isFalse((await confidentialAccountOf(account)).approved);
+
+    await approve(user);
+    assert.isTrue((await confidentialAccountOf(account)).approved);
+
+    // The approval is kept on-chain for audit
+    const record = await program.account.confidentialApproval.fetch(approval);
+    assert.ok(record.wrappedMint.equals(other.wrappedMint));
+    assert.ok(record.tokenAccount.equals(account));
+    assert.ok(record.owner.equals(user.publicKey));
+    assert.ok(record.approvedBy.equals(user.publicKey));
+    assert.isAbove(record.approvedAt.toNumber(), 0);
+  });
+
+  it("Wrap And Deposit: Lands In The Pending Confidential Balance", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const originalAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, user, mint, user.publicKey)).address;
+    await mintTo(provider.connection, user, mint, originalAccount, user.publicKey, 10_000);
+    const pdas = await initializeWrapper(mint);
+    const accounts = wrapAccountsFor(mint, pdas, TOKEN_PROGRAM_ID, originalAccount);
+
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      pdas.wrappedMint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await configureConfidential(mint, pdas, accounts.userWrappedAccount);
+    assert.equal((await confidentialAccountOf(accounts.userWrappedAccount)).pendingBalanceCreditCounter, 0n);
+
+    await program.methods.wrapAndDeposit(new anchor.BN(1_000), noFeeLimit, null).accounts(accounts).rpc();
+
+    // Nothing stays in the public balance; the deposit is credited to the pending balance
+    const wrapped = await getAccount(provider.connection, accounts.userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(wrapped.amount.toString(), "0");
+    assert.equal((await confidentialAccountOf(accounts.userWrappedAccount)).pendingBalanceCreditCounter, 1n);
+
+    const stats = await program.account.wrapperStats.fetch(pdas.wrapperStats);
+    assert.equal(stats.totalWrapped.toString(), "1000");
+    assert.equal(stats.totalDeposited.toString(), "1000");
+    const vaultAccount = await getAccount(provider.connection, pdas.vault);
+    assert.equal(vaultAccount.amount.toString(), "1000");
+  });
+
+  it("Unwrap Confidential: Rejects Accounts That Are Not Proof Contexts", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const originalAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, user, mint, user.publicKey)).address;
+    await mintTo(provider.connection, user, mint, originalAccount, user.publicKey, 10_000);
+    const pdas = await initializeWrapper(mint);
+    const accounts = wrapAccountsFor(mint, pdas, TOKEN_PROGRAM_ID, originalAccount);
+
+    await program.methods.wrap(new anchor.BN(1_000), noFeeLimit, null).accounts(accounts).rpc();
+    await configureConfidential(mint, pdas, accounts.userWrappedAccount);
+
+    // The withdraw must be backed by verified equality and range proof context accounts
+    try {
+      await program.methods.unwrapConfidential(new anchor.BN(500), Array.from(decryptableZeroBalance), noFeeLimit, null)
+        .accounts({
+          ...accounts,
+          equalityProofContext: user.publicKey,
+          rangeProofContext: user.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.notEqual(e.message, "Should have failed");
+    }
+
+    // Nothing was burned or released
+    const wrapped = await getAccount(provider.connection, accounts.userWrappedAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(wrapped.amount.toString(), "1000");
+    const vaultAccount = await getAccount(provider.connection, pdas.vault);
+    assert.equal(vaultAccount.amount.toString(), "1000");
+  });
+
+  it("Admin: Timelocked Actions Wait, Can Be Cancelled And Lapse With The Role", async () => {
+    // Separate wrapper so the delay does not slow down the other tests
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const other = await initializeWrapper(mint);
+    const queue = async (action: any) => {
+      const timelockedAction = await nextActionPda(other.wrapperConfig);
+      await program.methods.queueAction(action)
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          timelockedAction: timelockedAction,
+          proposer: user.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return timelockedAction;
+    };
+    const execute = (timelockedAction: anchor.web3.PublicKey) => executeQueued(timelockedAction, other);
+    const cancel = (timelockedAction: anchor.web3.PublicKey) =>
+      program.methods.cancelAction()
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          timelockedAction: timelockedAction,
+          proposer: user.publicKey,
+          canceller: user.publicKey,
+        })
+        .rpc();
+    const grantFeeManager = (account: anchor.web3.PublicKey) =>
+      program.methods.grantRole({ feeManager: {} }, account)
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          authority: user.publicKey,
+        })
+        .rpc();
+    const setFees = { setFees: { wrapFeeBps: 50, unwrapFeeBps: 50 } };
+
+    // An action queued by a key that has since lost the role is void
+    const stale = await queue(setFees);
+    await grantFeeManager(anchor.web3.Keypair.generate().publicKey);
+    try {
+      await execute(stale);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+    await grantFeeManager(user.publicKey);
+    await cancel(stale);
+    assert.isNull(await provider.connection.getAccountInfo(stale));
+
+    // With a one-hour delay, actions cannot run before their ETA
+    await execute(await queue({ setTimelockDelay: { timelockDelay: new anchor.BN(3600) } }));
+    const pending = await queue(setFees);
+    try {
+      await execute(pending);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "TimelockNotExpired");
+    }
+
+    // Cancelling closes the queued action without applying it
+    await cancel(pending);
+    assert.isNull(await provider.connection.getAccountInfo(pending));
+    const config = await program.account.wrapperConfig.fetch(other.wrapperConfig);
+    assert.equal(config.wrapFeeBps, 0);
+  });
+
+  it("Admin: Authority Handover Needs The Nominee And Can Be Cancelled", async () => {
+    const mint = await createMint(provider.connection, user, user.publicKey, null, decimals);
+    const other = await initializeWrapper(mint);
+    const nominee = anchor.web3.Keypair.generate();
+    const accept = (signer: anchor.web3.Keypair) =>
+      program.methods.acceptAuthority()
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          newAuthority: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    const cancelTransfer = () =>
+      program.methods.cancelAuthorityTransfer()
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          authority: user.publicKey,
+        })
+        .rpc();
+    const propose = async () => {
+      const timelockedAction = await nextActionPda(other.wrapperConfig);
+      await program.methods.proposeAuthority(nominee.publicKey)
+        .accounts({
+          wrapperConfig: other.wrapperConfig,
+          timelockedAction: timelockedAction,
+          proposer: user.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      // The nomination only lands once the queued action is executed
+      assert.isNull((await program.account.wrapperConfig.fetch(other.wrapperConfig)).pendingAuthority);
+      await executeQueued(timelockedAction, other);
+    };
+
+    // Only the pending authority can accept
+    await propose();
+    try {
+      await accept(anchor.web3.Keypair.generate());
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+
+    // A cancelled nomination can no longer be accepted
+    await cancelTransfer();
+    try {
+      await accept(nominee);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+    try {
+      await cancelTransfer();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "NoPendingAuthority");
+    }
+
+    await propose();
+    await accept(nominee);
+    const config = await program.account.wrapperConfig.fetch(other.wrapperConfig);
+    assert.ok(config.authority.equals(nominee.publicKey));
+    assert.isNull(config.pendingAuthority);
+  });
+
+  it("Admin: Can Sweep Surplus But Not Owed Fees", async () => {
+    // Vault balance not backing wrapped supply, referral balances or accrued fees
+    const surplusOf = async () => {
+      const stats = await program.account.wrapperStats.fetch(wrapperStats);
+      const owed = stats.totalWrapped.sub(stats.totalUnwrapped).add(stats.referralFeesOwed).add(stats.feesAccrued);
+      return (await getAccount(provider.connection, vault)).amount - BigInt(owed.toString());
+    };
+    const sweep = (amount: number) =>
+      program.methods.sweepSurplus(new anchor.BN(amount))
+        .accounts({
+          originalMint: originalMint,
+          wrapperConfig: wrapperConfig,
+          wrapperStats: wrapperStats,
+          vault: vault,
+          authority: user.publicKey,
+          destination: userOriginalAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    // Leave a referral share owed to a partner
+    const partner = anchor.web3.Keypair.generate();
+    const [partnerBalance] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), wrapperConfig.toBuffer(), partner.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods.registerReferrer(partner.publicKey)
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        referrerBalance: partnerBalance,
+        feeManager: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null)
+      .accounts(wrapAccounts({ referrerBalance: partnerBalance }))
+      .rpc();
+
+    // A direct donation to the vault is surplus
+    await transfer(provider.connection, user, userOriginalAccount, vault, user.publicKey, 500);
+    assert.equal((await surplusOf()).toString(), "500");
+
+    // Accrued fees and referral balances cannot be swept
+    try {
+      await sweep(501);
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InsufficientVaultBalance");
+    }
+
+    const before = await getAccount(provider.connection, userOriginalAccount);
+    await sweep(500);
+    const after = await getAccount(provider.connection, userOriginalAccount);
+    assert.equal((after.amount - before.amount).toString(), "500");
+    assert.equal((await surplusOf()).toString(), "0");
+  });
+
+  it("Admin: Can Withdraw Fees", async () => {
+    const treasurerTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      originalMint,
+      user.publicKey
+    )).address;
+
+    const withdraw = (amount: anchor.BN) =>
+      program.methods.withdrawFees(amount)
+        .accounts({
+          originalMint: originalMint,
+          wrapperConfig: wrapperConfig,
+          wrapperStats: wrapperStats,
+          feeSplit: feeSplit,
+          vault: vault,
+          treasurer: user.publicKey,
+          treasurerTokenAccount: treasurerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    // 1% fees were charged above and no fee split is set yet
+    const stats = await program.account.wrapperStats.fetch(wrapperStats);
+    assert.ok(stats.feesAccrued.gtn(1));
+
+    // Only accrued fees can be withdrawn, not the backing or referral balances
+    try {
+      await withdraw(stats.feesAccrued.addn(1));
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InsufficientAccruedFees");
+    }
+
+    await withdraw(new anchor.BN(1));
+
+    const after = await program.account.wrapperStats.fetch(wrapperStats);
+    assert.equal(after.feesAccrued.toString(), stats.feesAccrued.subn(1).toString());
+  });
+
+  it("Anyone: Can Distribute Fees To The Split", async () => {
+    // Recipients must be original-mint token accounts
+    try {
+      await program.methods.setFeeSplit([{ tokenAccount: userWrappedAccount, shareBps: 10_000 }])
+        .accounts({
+          wrapperConfig: wrapperConfig,
+          feeSplit: feeSplit,
+          treasurer: user.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([{ pubkey: userWrappedAccount, isSigner: false, isWritable: false }])
+        .rpc();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidFeeSplit");
+    }
+
+    await program.methods.setFeeSplit([{ tokenAccount: userOriginalAccount, shareBps: 10_000 }])
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        feeSplit: feeSplit,
+        treasurer: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([{ pubkey: userOriginalAccount, isSigner: false, isWritable: false }])
+      .rpc();
+
+    const before = await getAccount(provider.connection, userOriginalAccount);
+    await program.methods.distributeFees()
+      .accounts({
+        originalMint: originalMint,
+        wrapperConfig: wrapperConfig,
+        wrapperStats: wrapperStats,
+        feeSplit: feeSplit,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([{ pubkey: userOriginalAccount, isSigner: false, isWritable: true }])
+      .rpc();
+    const after = await getAccount(provider.connection, userOriginalAccount);
+    assert.ok(after.amount > before.amount); // 1% fees were charged above
+
+    // With a split in place the treasurer can no longer withdraw directly
+    const withdraw = () =>
+      program.methods.withdrawFees(new anchor.BN(1))
+        .accounts({
+          originalMint: originalMint,
+          wrapperConfig: wrapperConfig,
+          wrapperStats: wrapperStats,
+          feeSplit: feeSplit,
+          vault: vault,
+          treasurer: user.publicKey,
+          treasurerTokenAccount: userOriginalAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    try {
+      await withdraw();
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "FeeSplitConfigured");
+    }
+
+    // Clearing the split is timelocked and needs the split account
+    const timelockedAction = await nextActionPda(wrapperConfig);
+    await program.methods.queueAction({ clearFeeSplit: {} })
+      .accounts({
+        wrapperConfig: wrapperConfig,
+        timelockedAction: timelockedAction,
+        proposer: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    try {
+      await executeQueued(timelockedAction, { wrapperConfig, wrappedMint });
+      assert.fail("Should have failed");
+    } catch (e: any) {
+      assert.include(e.message, "FeeSplitNotConfigured");
+    }
+    await executeQueued(timelockedAction, { wrapperConfig, wrappedMint, feeSplit });
+    assert.isNull(await provider.connection.getAccountInfo(feeSplit));
+
+    // Fees accrued from here on can be withdrawn directly again
+    await program.methods.wrap(new anchor.BN(10_000), noFeeLimit, null).accounts(wrapAccounts()).rpc();
+    await withdraw();
   });
 });